@@ -1,9 +1,21 @@
 //! Control Utilities
 //!
 //! This module provides functions for controlling the console, including flushing the output buffer,
-//! clearing lines, and moving the cursor in various directions.
+//! clearing lines, moving the cursor in various directions and querying the size of the terminal.
+//!
+//! All output of this module and of [styled text](crate::styled) enables [ANSI support] on
+//! Windows before it's first printed.
+//!
+//! [ANSI support]: enable_ansi_support
+
+use std::{
+    env,
+    io::{self, Write},
+    sync::RwLock,
+};
 
-use std::io::{self, Write};
+/// Whether escape sequences are supported, `None` until first enabled.
+static ANSI_SUPPORT: RwLock<Option<bool>> = RwLock::new(None);
 
 /// Flushes the output buffer, ensuring that all content is written to the console.
 pub fn flush() {
@@ -15,18 +27,22 @@ pub fn flush() {
 /// This function uses ANSI escape codes to clear the entire line and move the cursor to the
 /// beginning of the line.
 pub fn clear_line() {
-    print!("\r\x1b[2K");
-    flush();
+    print_escape("\r\x1b[2K");
 }
 
 /// Clears the `i` lines in the console.
 pub fn clear_lines(i: usize) {
     for _ in 0..i {
-        print!("\r\x1b[2K");
-        flush();
+        print_escape("\r\x1b[2K");
     }
 }
 
+/// Clears the current line and all lines below it, moving the cursor to the beginning of the
+/// line.
+pub fn clear_below() {
+    print_escape("\r\x1b[J");
+}
+
 /// Struct for ensuring and changing cursor visibility.
 #[derive(Default)]
 pub struct Visibility;
@@ -38,14 +54,12 @@ impl Visibility {
 
     /// Hide the cursor via an ASCII escape sequence.
     pub fn hide_cursor(&self) {
-        print!("\x1B[?25l");
-        flush();
+        print_escape("\x1B[?25l");
     }
 
     /// Show the cursor via an ASCII escape sequence.
     pub fn show_cursor(&self) {
-        print!("\x1B[?25h");
-        flush();
+        print_escape("\x1B[?25h");
     }
 }
 
@@ -62,8 +76,7 @@ impl Drop for Visibility {
 /// * `n` - The number of lines to move the cursor down.
 pub fn move_cursor_down(n: usize) {
     if n > 0 {
-        print!("\x1b[{}B", n);
-        flush();
+        print_escape(&format!("\x1b[{}B", n));
     }
 }
 
@@ -74,8 +87,7 @@ pub fn move_cursor_down(n: usize) {
 /// * `n` - The number of lines to move the cursor up.
 pub fn move_cursor_up(n: usize) {
     if n > 0 {
-        print!("\x1b[{}A", n);
-        flush();
+        print_escape(&format!("\x1b[{}A", n));
     }
 }
 
@@ -86,8 +98,7 @@ pub fn move_cursor_up(n: usize) {
 /// * `n` - The number of characters to move the cursor to the left.
 pub fn move_cursor_left(n: usize) {
     if n > 0 {
-        print!("\x1b[{}D", n);
-        flush();
+        print_escape(&format!("\x1b[{}D", n));
     }
 }
 
@@ -98,8 +109,7 @@ pub fn move_cursor_left(n: usize) {
 /// * `n` - The number of characters to move the cursor to the right.
 pub fn move_cursor_right(n: usize) {
     if n > 0 {
-        print!("\x1b[{}C", n);
-        flush();
+        print_escape(&format!("\x1b[{}C", n));
     }
 }
 
@@ -110,6 +120,151 @@ pub fn move_cursor_right(n: usize) {
 /// * `x` - The horizontal position (column) to move the cursor to.
 /// * `y` - The vertical position (row) to move the cursor to.
 pub fn move_cursor_to(x: usize, y: usize) {
-    print!("\x1B[{};{}H", y + 1, x + 1);
+    print_escape(&format!("\x1B[{};{}H", y + 1, x + 1));
+}
+
+/// Prints an escape sequence, enabling ANSI support first.
+fn print_escape(sequence: &str) {
+    enable_ansi_support();
+    print!("{sequence}");
     flush();
 }
+
+/// Enables the processing of ANSI escape sequences by the console and returns whether they are
+/// supported.
+///
+/// Windows consoles print escape sequences literally unless virtual terminal processing is
+/// enabled for them, which this does for the standard output and error. It returns `false` if
+/// the console doesn't support it, e.g. before Windows 10, and colors should be disabled. Streams
+/// that aren't consoles, like pipes, files or terminal emulators like mintty, pass escape
+/// sequences on unchanged and count as supported. On other platforms, this always returns `true`.
+///
+/// It's called automatically before the first styled or control output and only enables the
+/// console once, later calls return the same result.
+///
+/// # Example
+///
+/// ```rust
+/// use console_utils::control::enable_ansi_support;
+///
+/// if !enable_ansi_support() {
+///     eprintln!("this console doesn't support colors");
+/// }
+/// ```
+pub fn enable_ansi_support() -> bool {
+    if let Some(supported) = *ANSI_SUPPORT.read().unwrap_or_else(|e| e.into_inner()) {
+        return supported;
+    }
+    let mut support = ANSI_SUPPORT.write().unwrap_or_else(|e| e.into_inner());
+    // another thread might have enabled it in the meantime
+    *support.get_or_insert_with(enable_virtual_terminal)
+}
+
+/// Enables virtual terminal processing for the standard output and error.
+#[cfg(windows)]
+fn enable_virtual_terminal() -> bool {
+    use windows_sys::Win32::System::Console::{
+        GetConsoleMode, GetStdHandle, SetConsoleMode, ENABLE_VIRTUAL_TERMINAL_PROCESSING,
+        STD_ERROR_HANDLE, STD_OUTPUT_HANDLE,
+    };
+
+    [STD_OUTPUT_HANDLE, STD_ERROR_HANDLE]
+        .into_iter()
+        .all(|std| {
+            let handle = unsafe { GetStdHandle(std) };
+            let mut mode = 0;
+            // SAFETY: GetConsoleMode fails for invalid handles and ones that aren't consoles.
+            if unsafe { GetConsoleMode(handle, &mut mode) } == 0 {
+                return true;
+            }
+            mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0
+                || unsafe { SetConsoleMode(handle, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING) } != 0
+        })
+}
+
+/// Terminals on other platforms always process escape sequences.
+#[cfg(not(windows))]
+fn enable_virtual_terminal() -> bool {
+    true
+}
+
+/// Returns whether both the standard input and output are connected to a terminal, so keys can
+/// be read one at a time and the cursor moved around.
+pub(crate) fn is_terminal() -> bool {
+    #[cfg(unix)]
+    {
+        is_input_terminal() && unsafe { libc::isatty(libc::STDOUT_FILENO) == 1 }
+    }
+
+    #[cfg(windows)]
+    {
+        is_input_terminal() && is_console(windows_sys::Win32::System::Console::STD_OUTPUT_HANDLE)
+    }
+}
+
+/// Returns whether the standard input is connected to a terminal, so keys can be read without
+/// being echoed, even if the output is redirected.
+pub(crate) fn is_input_terminal() -> bool {
+    #[cfg(unix)]
+    {
+        unsafe { libc::isatty(libc::STDIN_FILENO) == 1 }
+    }
+
+    #[cfg(windows)]
+    {
+        is_console(windows_sys::Win32::System::Console::STD_INPUT_HANDLE)
+    }
+}
+
+/// Returns whether the standard handle `std` is a console.
+#[cfg(windows)]
+fn is_console(std: windows_sys::Win32::System::Console::STD_HANDLE) -> bool {
+    use windows_sys::Win32::System::Console::{GetConsoleMode, GetStdHandle};
+
+    let mut mode = 0;
+    // SAFETY: GetConsoleMode fails for handles that aren't consoles.
+    unsafe { GetConsoleMode(GetStdHandle(std), &mut mode) != 0 }
+}
+
+/// Returns the size of the terminal as `(columns, rows)`.
+///
+/// Falls back to the `COLUMNS` and `LINES` environment variables if the output isn't a terminal,
+/// and returns `None` if the size can't be determined at all.
+pub fn terminal_size() -> Option<(usize, usize)> {
+    #[cfg(unix)]
+    {
+        for fd in [libc::STDOUT_FILENO, libc::STDERR_FILENO, libc::STDIN_FILENO] {
+            // SAFETY: zeroed winsize is filled by ioctl on success.
+            let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+            if unsafe { libc::ioctl(fd, libc::TIOCGWINSZ, &mut size) } == 0 && size.ws_col > 0 {
+                return Some((size.ws_col as usize, size.ws_row as usize));
+            }
+        }
+    }
+
+    #[cfg(windows)]
+    {
+        use windows_sys::Win32::System::Console::{
+            GetConsoleScreenBufferInfo, GetStdHandle, CONSOLE_SCREEN_BUFFER_INFO, STD_OUTPUT_HANDLE,
+        };
+
+        // SAFETY: zeroed info is filled by GetConsoleScreenBufferInfo on success.
+        let mut info: CONSOLE_SCREEN_BUFFER_INFO = unsafe { std::mem::zeroed() };
+        let handle = unsafe { GetStdHandle(STD_OUTPUT_HANDLE) };
+        if unsafe { GetConsoleScreenBufferInfo(handle, &mut info) } != 0 {
+            let window = info.srWindow;
+            return Some((
+                (window.Right - window.Left + 1) as usize,
+                (window.Bottom - window.Top + 1) as usize,
+            ));
+        }
+    }
+
+    let var = |name| {
+        env::var(name)
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+            .filter(|v| *v > 0)
+    };
+    Some((var("COLUMNS")?, var("LINES").unwrap_or(24)))
+}
@@ -0,0 +1,685 @@
+//! Diagnostic Utilities
+//!
+//! This module provides [`Diagnostic`]s for reporting problems in source files, like invalid
+//! entries of a configuration file, in the style of compiler errors: the offending lines are
+//! printed with line numbers and the [`Label`]led spans underlined below them, followed by notes
+//! and help messages. Columns are measured in terminal cells, so tabs, East Asian wide
+//! characters and emoji are pointed at correctly. TOML and JSON sources are highlighted with the
+//! value styles of the current [`theme`].
+
+use std::{fmt, ops::Range};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    styled::{display_width, grapheme_width, Color, Style, StyledString, Underline},
+    theme::{theme, Theme},
+};
+
+/// The severity of a [`Diagnostic`], deciding its color and the word it starts with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// A problem that must be fixed, red.
+    #[default]
+    Error,
+    /// A possible problem, yellow.
+    Warning,
+    /// Additional information, green.
+    Note,
+    /// A suggestion how to fix a problem, cyan.
+    Help,
+}
+
+impl Severity {
+    /// Returns the lowercase name printed before messages of this severity.
+    pub fn name(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+            Severity::Help => "help",
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Severity::Error => Color::BrightRed,
+            Severity::Warning => Color::BrightYellow,
+            Severity::Note => Color::BrightGreen,
+            Severity::Help => Color::BrightCyan,
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// How labelled spans are marked in the source.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Marker {
+    /// A line of `^` below primary and `-` below secondary spans.
+    #[default]
+    Caret,
+    /// A curly underline of the spans themselves, with the label messages below their start.
+    ///
+    /// Terminals without [extended underlines](crate::styled::Capabilities::extended_underline)
+    /// show a straight one instead.
+    Curly,
+}
+
+/// The language the source of a [`Diagnostic`] is highlighted as.
+///
+/// Sources are highlighted line by line, so strings spanning several lines are only highlighted
+/// on their first one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Language {
+    /// No highlighting.
+    #[default]
+    Plain,
+    /// TOML, with highlighted keys, table headers, strings, numbers, booleans and comments.
+    Toml,
+    /// JSON, with highlighted keys, strings, numbers, booleans and `null`.
+    Json,
+}
+
+impl Language {
+    /// Returns the language of a file by the extension of its `path`, [`Plain`](Language::Plain)
+    /// for unknown ones.
+    pub fn from_path(path: &str) -> Self {
+        let extension = path.rsplit_once('.').map_or("", |(_, extension)| extension);
+        match extension.to_ascii_lowercase().as_str() {
+            "toml" => Language::Toml,
+            "json" => Language::Json,
+            _ => Language::Plain,
+        }
+    }
+}
+
+/// A message attached to a span of the source of a [`Diagnostic`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Label {
+    span: Range<usize>,
+    message: String,
+    primary: bool,
+}
+
+impl Label {
+    /// Creates a label pointing at the cause of the problem, in the color of the severity.
+    ///
+    /// The span is a range of byte offsets into the source. Empty spans point at the character
+    /// at their position.
+    pub fn primary(span: Range<usize>, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+            primary: true,
+        }
+    }
+
+    /// Creates a label giving context to the problem, in blue.
+    pub fn secondary(span: Range<usize>, message: impl Into<String>) -> Self {
+        Self {
+            primary: false,
+            ..Self::primary(span, message)
+        }
+    }
+
+    /// Returns the range of byte offsets the label points at.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Returns the message of the label.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+/// A problem in a source file, rendered like a compiler error.
+///
+/// # Example
+///
+/// ```rust
+/// use console_utils::diagnostic::{Diagnostic, Label};
+///
+/// let source = "[server]\nport = \"eighty\"\n";
+/// let diagnostic = Diagnostic::error("invalid port")
+///     .code("E001")
+///     .source("config.toml", source)
+///     .label(Label::primary(16..24, "expected a number"))
+///     .label(Label::secondary(0..8, "in this table"))
+///     .note("ports range from 1 to 65535")
+///     .help("try `port = 8080`");
+/// eprintln!("{diagnostic}");
+/// assert_eq!(
+///     diagnostic.render().plain_text(),
+///     "error[E001]: invalid port\n \
+///       --> config.toml:2:8\n  \
+///        |\n\
+///      1 | [server]\n  \
+///        | -------- in this table\n\
+///      2 | port = \"eighty\"\n  \
+///        |        ^^^^^^^^ expected a number\n  \
+///        |\n  \
+///        = note: ports range from 1 to 65535\n  \
+///        = help: try `port = 8080`"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    severity: Severity,
+    code: Option<String>,
+    message: String,
+    source: Option<(String, String)>,
+    labels: Vec<Label>,
+    footer: Vec<(Severity, String)>,
+    marker: Marker,
+    language: Option<Language>,
+}
+
+impl Diagnostic {
+    /// Creates a diagnostic with the given severity and message.
+    pub fn new(severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            code: None,
+            message: message.into(),
+            source: None,
+            labels: Vec::new(),
+            footer: Vec::new(),
+            marker: Marker::default(),
+            language: None,
+        }
+    }
+
+    /// Creates an error.
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, message)
+    }
+
+    /// Creates a warning.
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, message)
+    }
+
+    /// Sets a code identifying the kind of problem, printed in brackets after the severity.
+    pub fn code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Sets the source the labels point into and the name, usually the path, it's shown with.
+    pub fn source(mut self, name: impl Into<String>, text: impl Into<String>) -> Self {
+        self.source = Some((name.into(), text.into()));
+        self
+    }
+
+    /// Adds a label to a span of the source.
+    pub fn label(mut self, label: Label) -> Self {
+        self.labels.push(label);
+        self
+    }
+
+    /// Adds a note below the source.
+    pub fn note(mut self, note: impl Into<String>) -> Self {
+        self.footer.push((Severity::Note, note.into()));
+        self
+    }
+
+    /// Adds a help message below the source, e.g. suggesting a fix.
+    pub fn help(mut self, help: impl Into<String>) -> Self {
+        self.footer.push((Severity::Help, help.into()));
+        self
+    }
+
+    /// Sets how the labelled spans are marked.
+    pub fn marker(mut self, marker: Marker) -> Self {
+        self.marker = marker;
+        self
+    }
+
+    /// Sets the language the source is highlighted as, instead of the one its name has the
+    /// extension of.
+    pub fn language(mut self, language: Language) -> Self {
+        self.language = Some(language);
+        self
+    }
+
+    /// Returns the severity.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Renders the diagnostic into a styled string.
+    pub fn render(&self) -> StyledString {
+        let color = Style::new().fg(self.severity.color()).bold();
+        let mut out = StyledString::new();
+        out.push_str(self.severity.name(), color);
+        if let Some(code) = &self.code {
+            out.push_str(&format!("[{code}]"), color);
+        }
+        out.push_str(": ", Style::new().bold());
+        out.push_str(&self.message, Style::new().bold());
+
+        let source = self.source.as_ref().map(|(name, text)| {
+            let language = self.language.unwrap_or_else(|| Language::from_path(name));
+            let source = Source::new(text, language);
+            let marks = source.marks(&self.labels);
+            (name, source, marks)
+        });
+        let gutter = match &source {
+            Some((_, _, marks)) => marks
+                .last()
+                .map_or(0, |(line, _)| (line + 1).to_string().len()),
+            None => 0,
+        };
+        let gutter_style = Style::new().fg(Color::BrightBlue).bold();
+        let bar = |out: &mut StyledString| {
+            out.push_str("\n", Style::new());
+            out.push_str(&" ".repeat(gutter + 1), Style::new());
+            out.push_str("|", gutter_style);
+        };
+
+        if let Some((name, source, marks)) = &source {
+            let location = self
+                .labels
+                .iter()
+                .find(|label| label.primary)
+                .or_else(|| self.labels.first())
+                .map(|label| source.location(label.span.start));
+            out.push_str("\n", Style::new());
+            out.push_str(&" ".repeat(gutter), Style::new());
+            out.push_str("--> ", gutter_style);
+            out.push_str(name, Style::new());
+            if let Some((line, column)) = location {
+                out.push_str(&format!(":{}:{}", line + 1, column + 1), Style::new());
+            }
+
+            if !marks.is_empty() {
+                bar(&mut out);
+            }
+            let mut previous: Option<usize> = None;
+            for (line, marks) in marks {
+                if previous.map_or(false, |previous| line - previous > 1) {
+                    out.push_str("\n", Style::new());
+                    out.push_str("...", gutter_style);
+                }
+                previous = Some(*line);
+                self.write_line(&mut out, source, *line, marks, gutter, gutter_style);
+            }
+            if !self.footer.is_empty() && !marks.is_empty() {
+                bar(&mut out);
+            }
+        }
+
+        for (severity, message) in &self.footer {
+            let prefix = format!("= {severity}: ");
+            out.push_str("\n", Style::new());
+            out.push_str(&" ".repeat(gutter + 1), Style::new());
+            out.push_str("= ", gutter_style);
+            out.push_str(severity.name(), Style::new().bold());
+            out.push_str(": ", Style::new());
+            let indent = " ".repeat(gutter + 1 + prefix.len());
+            out.push_str(&message.replace('\n', &format!("\n{indent}")), Style::new());
+        }
+        out
+    }
+
+    /// Appends a source line with the marks of the labels on it below.
+    fn write_line(
+        &self,
+        out: &mut StyledString,
+        source: &Source,
+        line: usize,
+        marks: &[Mark],
+        gutter: usize,
+        gutter_style: Style,
+    ) {
+        let color = |mark: &Mark| match self.labels[mark.label].primary {
+            true => self.severity.color(),
+            false => Color::BrightBlue,
+        };
+        let style = |mark: &Mark| Style::new().fg(color(mark)).bold();
+
+        out.push_str("\n", Style::new());
+        out.push_str(&format!("{:>gutter$} | ", line + 1), gutter_style);
+        let text = source.line(line);
+        let tokens = source.highlight(line);
+        let mut pos = 0;
+        if self.marker == Marker::Curly {
+            let mut sorted: Vec<&Mark> = marks.iter().collect();
+            sorted.sort_by_key(|mark| mark.bytes.start);
+            for mark in sorted {
+                let start = mark.bytes.start.max(pos);
+                let end = mark.bytes.end.max(start);
+                write_source(out, text, pos..start, &tokens, |style| style);
+                write_source(out, text, start..end, &tokens, |style| {
+                    style
+                        .underline_style(Underline::Curly)
+                        .underline_color(color(mark))
+                });
+                pos = end;
+            }
+        }
+        write_source(out, text, pos..text.len(), &tokens, |style| style);
+
+        // the marks with a message to show, the last one is shown on the line of the marks
+        let mut messages: Vec<&Mark> = marks
+            .iter()
+            .filter(|mark| mark.last && !self.labels[mark.label].message.is_empty())
+            .collect();
+        let mut pieces: Vec<(usize, String, Style)> = Vec::new();
+        match self.marker {
+            Marker::Caret => {
+                for mark in marks {
+                    let primary = self.labels[mark.label].primary;
+                    let char = if primary { "^" } else { "-" };
+                    pieces.push((mark.col, char.repeat(mark.width), style(mark)));
+                }
+                let end = marks.iter().map(|m| m.col + m.width).max().unwrap_or(0);
+                if let Some(mark) = messages.last().filter(|m| m.col + m.width == end) {
+                    let message = &self.labels[mark.label].message;
+                    pieces.push((end + 1, message.clone(), style(mark)));
+                    messages.pop();
+                }
+            }
+            Marker::Curly => {
+                if let Some(mark) = messages.pop() {
+                    let message = &self.labels[mark.label].message;
+                    pieces.push((mark.col, message.clone(), style(mark)));
+                }
+                for mark in &messages {
+                    pieces.push((mark.col, "|".into(), style(mark)));
+                }
+            }
+        }
+        if pieces.is_empty() {
+            return;
+        }
+        write_pieces(out, pieces, gutter, gutter_style);
+
+        // the remaining messages below each other, right to left
+        if !messages.is_empty() && self.marker == Marker::Caret {
+            let connectors = messages
+                .iter()
+                .map(|mark| (mark.col, "|".to_string(), style(mark)))
+                .collect();
+            write_pieces(out, connectors, gutter, gutter_style);
+        }
+        while let Some(mark) = messages.pop() {
+            let mut pieces: Vec<_> = messages
+                .iter()
+                .map(|mark| (mark.col, "|".to_string(), style(mark)))
+                .collect();
+            let message = &self.labels[mark.label].message;
+            pieces.push((mark.col, message.clone(), style(mark)));
+            write_pieces(out, pieces, gutter, gutter_style);
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+/// The part of a label on a single line.
+struct Mark {
+    /// The index of the label.
+    label: usize,
+    /// The marked bytes of the line.
+    bytes: Range<usize>,
+    /// The column the mark starts at and its width in terminal cells.
+    col: usize,
+    width: usize,
+    /// Whether this is the last line of the label, which shows its message.
+    last: bool,
+}
+
+/// A source text split into lines.
+struct Source<'a> {
+    text: &'a str,
+    /// The byte offsets of the line starts.
+    starts: Vec<usize>,
+    language: Language,
+    theme: Theme,
+}
+
+impl<'a> Source<'a> {
+    fn new(text: &'a str, language: Language) -> Self {
+        let starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            text,
+            starts,
+            language,
+            theme: theme(),
+        }
+    }
+
+    /// Returns the line at `index`, without its line break.
+    fn line(&self, index: usize) -> &'a str {
+        let start = self.starts[index];
+        let end = self
+            .starts
+            .get(index + 1)
+            .map_or(self.text.len(), |e| e - 1);
+        self.text[start..end].trim_end_matches('\r')
+    }
+
+    /// Returns the line and the character in the line at the byte `offset`.
+    fn location(&self, offset: usize) -> (usize, usize) {
+        let offset = self.floor(offset);
+        let line = self.starts.partition_point(|start| *start <= offset) - 1;
+        let column = self.text[self.starts[line]..offset].chars().count();
+        (line, column)
+    }
+
+    /// Returns the closest character boundary at or before `offset`.
+    fn floor(&self, offset: usize) -> usize {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        offset
+    }
+
+    /// Splits the labels into marks per line, ordered by line.
+    fn marks(&self, labels: &[Label]) -> Vec<(usize, Vec<Mark>)> {
+        let mut lines: Vec<(usize, Vec<Mark>)> = Vec::new();
+        for (index, label) in labels.iter().enumerate() {
+            let start = self.floor(label.span.start);
+            let end = self.floor(label.span.end).max(start);
+            let (first, _) = self.location(start);
+            let (last, _) = self.location(end.saturating_sub(1).max(start));
+            for line in first..=last {
+                let text = self.line(line);
+                let line_start = self.starts[line];
+                // spans starting on the `\n` of a `\r\n` lie past the trimmed line
+                let from = (start.max(line_start) - line_start).min(text.len());
+                let to = (end.max(line_start) - line_start).clamp(from, text.len());
+                // empty spans and line breaks still get a mark
+                let mut to = to;
+                if from == to {
+                    to = text[from..]
+                        .chars()
+                        .next()
+                        .map_or(from, |c| from + c.len_utf8());
+                }
+                let col = display_width(&expand_tabs(&text[..from]));
+                let width = display_width(&expand_tabs(&text[from..to])).max(1);
+                let mark = Mark {
+                    label: index,
+                    bytes: from..to,
+                    col,
+                    width,
+                    last: line == last,
+                };
+                match lines.iter_mut().find(|(l, _)| *l == line) {
+                    Some((_, marks)) => marks.push(mark),
+                    None => lines.push((line, vec![mark])),
+                }
+            }
+        }
+        lines.sort_by_key(|(line, _)| *line);
+        for (_, marks) in &mut lines {
+            marks.sort_by_key(|mark| mark.col);
+        }
+        lines
+    }
+}
+
+impl Source<'_> {
+    /// Returns the highlighted tokens of the line at `index`, as ordered byte ranges with their
+    /// styles.
+    fn highlight(&self, index: usize) -> Vec<(Range<usize>, Style)> {
+        let line = self.line(index);
+        let theme = &self.theme;
+        let toml = self.language == Language::Toml;
+        let mut tokens = Vec::new();
+        if self.language == Language::Plain {
+            return tokens;
+        }
+        // whether the token ending at `end` is followed by the separator of keys and values
+        let is_key = |end: usize| {
+            let separator = if toml { '=' } else { ':' };
+            line[end..].trim_start().starts_with(separator)
+        };
+
+        let mut i = 0;
+        while let Some(c) = line[i..].chars().next() {
+            let rest = &line[i..];
+            let (len, style) = if toml && c == '#' {
+                (rest.len(), Some(theme.hint_style))
+            } else if toml && c == '[' && line[..i].trim().is_empty() {
+                let header = rest.split('#').next().unwrap_or(rest).trim_end();
+                (header.len(), Some(theme.value_key_style))
+            } else if c == '"' || (toml && c == '\'') {
+                let len = string_len(rest, c);
+                match is_key(i + len) {
+                    true => (len, Some(theme.value_key_style)),
+                    false => (len, Some(theme.value_string_style)),
+                }
+            } else if c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '+') {
+                // bare keys, numbers, dates and literals, with the characters all of them use
+                let len = rest
+                    .find(|c: char| {
+                        !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '+' | '.' | ':'))
+                    })
+                    .unwrap_or(rest.len());
+                let word = &rest[..len];
+                let number = word.trim_start_matches(['+', '-']);
+                let style = match word {
+                    _ if toml && is_key(i + len) => Some(theme.value_key_style),
+                    "true" | "false" => Some(theme.value_bool_style),
+                    "null" if !toml => Some(theme.value_null_style),
+                    "inf" | "nan" | "+inf" | "-inf" | "+nan" | "-nan" if toml => {
+                        Some(theme.value_number_style)
+                    }
+                    _ if number.starts_with(|c: char| c.is_ascii_digit()) => {
+                        Some(theme.value_number_style)
+                    }
+                    _ => None,
+                };
+                (len, style)
+            } else {
+                (c.len_utf8(), None)
+            };
+            if let Some(style) = style {
+                tokens.push((i..i + len, style));
+            }
+            i += len;
+        }
+        tokens
+    }
+}
+
+/// Returns the length of the string starting with the `quote` at the start of `text`, up to the
+/// end of `text` if it isn't closed. Double quoted strings can contain escaped quotes.
+fn string_len(text: &str, quote: char) -> usize {
+    let triple: String = std::iter::repeat(quote).take(3).collect();
+    if text.starts_with(&triple) {
+        return text[3..].find(&triple).map_or(text.len(), |end| end + 6);
+    }
+    let mut escaped = false;
+    for (i, c) in text.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quote == '"' => escaped = true,
+            _ if c == quote => return i + 1,
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+/// Appends the `range` of a source line, with the styles of the highlighted `tokens` in it
+/// changed by `style`.
+fn write_source(
+    out: &mut StyledString,
+    line: &str,
+    range: Range<usize>,
+    tokens: &[(Range<usize>, Style)],
+    style: impl Fn(Style) -> Style,
+) {
+    let mut pos = range.start;
+    for (token, token_style) in tokens {
+        let start = token.start.clamp(pos, range.end);
+        let end = token.end.clamp(start, range.end);
+        if start < end {
+            out.push_str(&expand_tabs(&line[pos..start]), style(Style::new()));
+            out.push_str(&expand_tabs(&line[start..end]), style(*token_style));
+            pos = end;
+        }
+    }
+    if pos < range.end {
+        out.push_str(&expand_tabs(&line[pos..range.end]), style(Style::new()));
+    }
+}
+
+/// Replaces tabs with four spaces, so they have a known width.
+fn expand_tabs(text: &str) -> String {
+    text.replace('\t', "    ")
+}
+
+/// Appends a line below the source, with each piece starting at its column. Pieces overlapping
+/// the previous one are cut off at the start, and padded if that splits a wide character.
+fn write_pieces(
+    out: &mut StyledString,
+    mut pieces: Vec<(usize, String, Style)>,
+    gutter: usize,
+    gutter_style: Style,
+) {
+    pieces.sort_by_key(|(col, _, _)| *col);
+    out.push_str("\n", Style::new());
+    out.push_str(&format!("{:gutter$} |", ""), gutter_style);
+    out.push_str(" ", Style::new());
+    let mut pos = 0;
+    for (col, text, style) in pieces {
+        if col > pos {
+            out.push_str(&" ".repeat(col - pos), Style::new());
+            pos = col;
+        }
+        // skip the cells already covered, which may end in the middle of a wide character
+        let mut skipped = col;
+        let mut graphemes = text.graphemes(true).peekable();
+        while skipped < pos {
+            match graphemes.next() {
+                Some(grapheme) => skipped += grapheme_width(grapheme),
+                None => break,
+            }
+        }
+        if skipped > pos && graphemes.peek().is_some() {
+            out.push_str(&" ".repeat(skipped - pos), Style::new());
+            pos = skipped;
+        }
+        let text: String = graphemes.collect();
+        pos += display_width(&text);
+        out.push_str(&text, style);
+    }
+}
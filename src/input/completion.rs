@@ -0,0 +1,160 @@
+//! Tab Completion
+//!
+//! Completers suggest candidates for the text before the cursor in [`Input`](super::Input)
+//! prompts. The first Tab completes the prefix shared by all candidates, a second one lists them
+//! below the prompt.
+
+use std::{fs, path::is_separator};
+
+/// Suggests completions for the line being edited in an [`Input`](super::Input).
+///
+/// # Example
+///
+/// ```
+/// use console_utils::input::Completer;
+///
+/// /// Completes the line with the names of the days.
+/// struct Days;
+///
+/// impl Completer for Days {
+///     fn complete(&self, line: &str, cursor: usize) -> (usize, Vec<String>) {
+///         let days = ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday"];
+///         let typed = line[..cursor].to_lowercase();
+///         let candidates = days.iter().filter(|day| day.to_lowercase().starts_with(&typed));
+///         (0, candidates.map(|day| day.to_string()).collect())
+///     }
+/// }
+///
+/// assert_eq!(Days.complete("t", 1), (0, vec!["Tuesday".into(), "Thursday".into()]));
+/// ```
+pub trait Completer {
+    /// Returns the candidates for the text before the byte offset `cursor` in `line`, together
+    /// with the byte offset where the text they replace starts.
+    fn complete(&self, line: &str, cursor: usize) -> (usize, Vec<String>);
+}
+
+impl<F> Completer for F
+where
+    F: Fn(&str, usize) -> (usize, Vec<String>),
+{
+    fn complete(&self, line: &str, cursor: usize) -> (usize, Vec<String>) {
+        self(line, cursor)
+    }
+}
+
+/// Completes the word before the cursor from a fixed list of words.
+///
+/// # Example
+///
+/// ```
+/// use console_utils::input::{Completer, WordCompleter};
+///
+/// let completer = WordCompleter::new(["build", "bench", "check"]);
+/// assert_eq!(completer.complete("cargo b", 7), (6, vec!["build".into(), "bench".into()]));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WordCompleter {
+    words: Vec<String>,
+}
+
+impl WordCompleter {
+    /// Creates a completer suggesting `words`, in their order.
+    pub fn new<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            words: words.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl Completer for WordCompleter {
+    fn complete(&self, line: &str, cursor: usize) -> (usize, Vec<String>) {
+        let start = word_start(line, cursor);
+        let word = &line[start..cursor];
+        let candidates = self.words.iter().filter(|w| w.starts_with(word));
+        (start, candidates.cloned().collect())
+    }
+}
+
+/// Completes the path before the cursor with the files and directories on disk. Directories end
+/// with a separator, so they can be completed further, and hidden files are only suggested once a
+/// `.` is typed.
+///
+/// # Example
+///
+/// ```no_run
+/// use console_utils::input::{Input, PathCompleter};
+///
+/// let path: String = Input::new("File").completer(PathCompleter::new()).read();
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PathCompleter;
+
+impl PathCompleter {
+    /// Creates a completer for paths relative to the working directory or absolute ones.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Completer for PathCompleter {
+    fn complete(&self, line: &str, cursor: usize) -> (usize, Vec<String>) {
+        let start = word_start(line, cursor);
+        let path = &line[start..cursor];
+        let (dir, name) = match path.rfind(is_separator) {
+            Some(i) => path.split_at(i + 1),
+            None => ("", path),
+        };
+        let entries = match fs::read_dir(if dir.is_empty() { "." } else { dir }) {
+            Ok(entries) => entries,
+            Err(_) => return (start, Vec::new()),
+        };
+
+        let mut candidates: Vec<String> = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let file_name = entry.file_name().into_string().ok()?;
+                if !file_name.starts_with(name)
+                    || file_name.starts_with('.') && !name.starts_with('.')
+                {
+                    return None;
+                }
+                let separator = if entry.path().is_dir() {
+                    std::path::MAIN_SEPARATOR.to_string()
+                } else {
+                    String::new()
+                };
+                Some(format!("{dir}{file_name}{separator}"))
+            })
+            .collect();
+        candidates.sort();
+        (start, candidates)
+    }
+}
+
+/// Returns the start of the whitespace separated word ending at `cursor`.
+fn word_start(line: &str, cursor: usize) -> usize {
+    line[..cursor].rfind(char::is_whitespace).map_or(0, |i| {
+        i + line[i..].chars().next().map_or(1, char::len_utf8)
+    })
+}
+
+/// Returns the longest prefix shared by all `candidates`.
+pub(super) fn common_prefix(candidates: &[String]) -> &str {
+    let (first, rest) = match candidates.split_first() {
+        Some(split) => split,
+        None => return "",
+    };
+    let mut end = first.len();
+    for candidate in rest {
+        end = first[..end]
+            .char_indices()
+            .zip(candidate.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(end.min(candidate.len()), |((i, _), _)| i);
+    }
+    &first[..end]
+}
@@ -0,0 +1,441 @@
+//! Line Editing
+//!
+//! The raw-mode line editor behind [`input`](super::input), reading keys with
+//! [`read_key`] and redrawing the edited line with the [`control`](crate::control) cursor
+//! functions. It understands the usual readline keys:
+//!
+//! - Left and Right or Ctrl-B and Ctrl-F move by character, Alt-B and Alt-F by word.
+//! - Home and End or Ctrl-A and Ctrl-E move to the start and end of the line.
+//! - Backspace and Delete remove the character before and under the cursor.
+//! - Ctrl-W removes the word before the cursor, Ctrl-U and Ctrl-K everything before and after it.
+//! - Insert switches between inserting and overwriting characters.
+//! - Up and Down or Ctrl-P and Ctrl-N recall older and newer lines of the [`History`](super::History).
+//! - Tab completes the text before the cursor with a [`Completer`], a second Tab lists the
+//!   candidates.
+//! - Ctrl-R searches the history backwards while typing, again for older matches. Enter accepts the
+//!   match, Escape or Ctrl-G cancel the search and other keys continue editing the match.
+
+use std::io;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::completion::{common_prefix, Completer};
+use crate::{
+    control::{clear_below, flush, move_cursor_right, move_cursor_up, terminal_size},
+    read::{read_key, Key, RawMode},
+    styled::{display_width, grapheme_width, segments, Segment},
+};
+
+/// The text being edited and the position of the cursor in it.
+#[derive(Debug, Default)]
+pub(super) struct Editor {
+    buffer: String,
+    /// The byte offset of the cursor, always at a grapheme boundary.
+    cursor: usize,
+    /// Whether typed characters replace the ones under the cursor.
+    overwrite: bool,
+}
+
+impl Editor {
+    /// Applies an editing key, returns whether it was one.
+    pub(super) fn handle(&mut self, key: &Key) -> bool {
+        match key {
+            Key::Char(c) if !c.is_control() => self.insert(*c),
+            Key::ArrowLeft | Key::Ctrl('b') => self.cursor = self.previous(),
+            Key::ArrowRight | Key::Ctrl('f') => self.cursor = self.next(),
+            Key::Home | Key::Ctrl('a') => self.cursor = 0,
+            Key::End | Key::Ctrl('e') => self.cursor = self.buffer.len(),
+            Key::Alt('b') => self.cursor = self.word_start(is_word),
+            Key::Alt('f') => self.cursor = self.word_end(),
+            Key::Backspace => self.delete(self.previous()..self.cursor),
+            Key::Delete | Key::Ctrl('d') => self.delete(self.cursor..self.next()),
+            Key::Ctrl('w') => self.delete(self.word_start(|g| !is_space(g))..self.cursor),
+            Key::Ctrl('u') => self.delete(0..self.cursor),
+            Key::Ctrl('k') => self.delete(self.cursor..self.buffer.len()),
+            Key::Insert => self.overwrite = !self.overwrite,
+            _ => return false,
+        }
+        true
+    }
+
+    /// Replaces the text with `text`, placing the cursor at its end.
+    fn set(&mut self, text: &str) {
+        self.buffer = text.to_string();
+        self.cursor = text.len();
+    }
+
+    /// Replaces `range` of the buffer with `text`, placing the cursor behind it.
+    fn replace(&mut self, range: std::ops::Range<usize>, text: &str) {
+        self.cursor = range.start + text.len();
+        self.buffer.replace_range(range, text);
+    }
+
+    /// Inserts `c` at the cursor, replacing the character under it when overwriting.
+    fn insert(&mut self, c: char) {
+        if self.overwrite {
+            self.delete(self.cursor..self.next());
+        }
+        self.buffer.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    /// Removes `range` of the buffer and moves the cursor to its start.
+    fn delete(&mut self, range: std::ops::Range<usize>) {
+        self.cursor = range.start;
+        self.buffer.replace_range(range, "");
+    }
+
+    /// Returns the start of the grapheme before the cursor.
+    fn previous(&self) -> usize {
+        self.buffer[..self.cursor]
+            .grapheme_indices(true)
+            .next_back()
+            .map_or(0, |(i, _)| i)
+    }
+
+    /// Returns the end of the grapheme under the cursor.
+    fn next(&self) -> usize {
+        self.buffer[self.cursor..]
+            .graphemes(true)
+            .next()
+            .map_or(self.cursor, |g| self.cursor + g.len())
+    }
+
+    /// Returns the start of the word before the cursor, skipping the non-word characters
+    /// directly before it.
+    fn word_start(&self, is_word: fn(&str) -> bool) -> usize {
+        self.buffer[..self.cursor]
+            .grapheme_indices(true)
+            .rev()
+            .skip_while(|(_, g)| !is_word(g))
+            .take_while(|(_, g)| is_word(g))
+            .last()
+            .map_or(0, |(i, _)| i)
+    }
+
+    /// Returns the end of the word after the cursor, skipping the non-word characters directly
+    /// after it.
+    fn word_end(&self) -> usize {
+        self.buffer[self.cursor..]
+            .grapheme_indices(true)
+            .skip_while(|(_, g)| !is_word(g))
+            .take_while(|(_, g)| is_word(g))
+            .last()
+            .map_or(self.buffer.len(), |(i, g)| self.cursor + i + g.len())
+    }
+}
+
+/// Whether the grapheme is part of a word for word motions.
+fn is_word(grapheme: &str) -> bool {
+    grapheme.chars().any(char::is_alphanumeric)
+}
+
+/// Whether the grapheme separates words for Ctrl-W.
+fn is_space(grapheme: &str) -> bool {
+    grapheme.chars().all(char::is_whitespace)
+}
+
+/// Draws the prompt and the edited line, which may span several rows of the terminal, and
+/// lines below it like the completion menu.
+#[derive(Debug, Default)]
+pub(super) struct Screen {
+    /// The row of the cursor, relative to the row the prompt starts on.
+    row: usize,
+    /// Whether lines are shown below the edited line.
+    below: bool,
+}
+
+impl Screen {
+    /// Redraws the prompt and the text, followed by the lines `below`, placing the cursor at the
+    /// byte offset `cursor` of the text.
+    pub(super) fn draw(&mut self, prompt: &str, text: &str, cursor: usize, below: &str) {
+        let width = terminal_size().map_or(usize::MAX, |(columns, _)| columns);
+        move_cursor_up(self.row);
+        clear_below();
+        print!("{prompt}{text}");
+
+        let (row, col) = position(&[prompt, text], width);
+        let mut end = row;
+        for line in below.lines() {
+            print!("\r\n{line}");
+            end += 1 + position(&[line], width).0;
+        }
+        // a full last row leaves the cursor behind its last column until something follows
+        if col >= width && below.is_empty() {
+            println!();
+            end += 1;
+        }
+
+        let (mut row, mut col) = position(&[prompt, &text[..cursor]], width);
+        let next = text[cursor..].graphemes(true).next();
+        if col >= width || col + next.map_or(0, grapheme_width) > width {
+            (row, col) = (row + 1, 0);
+        }
+        move_cursor_up(end - row);
+        print!("\r");
+        move_cursor_right(col);
+        flush();
+        self.row = row;
+        self.below = !below.is_empty();
+    }
+
+    /// Removes the lines below the text, moves the cursor behind the text and onto the next line.
+    /// Returns the number of rows of the prompt and the text.
+    pub(super) fn finish(&mut self, prompt: &str, text: &str) -> usize {
+        if self.below {
+            self.draw(prompt, text, text.len(), "");
+        }
+        let width = terminal_size().map_or(usize::MAX, |(columns, _)| columns);
+        let (row, _) = position(&[prompt, text], width);
+        print!("\r");
+        for _ in self.row..=row {
+            println!();
+        }
+        flush();
+        row + 1
+    }
+}
+
+/// Lays out completion candidates in columns below the prompt, at most [`MENU_ROWS`] rows.
+fn menu(candidates: &[String], width: usize) -> String {
+    let column = candidates
+        .iter()
+        .map(|c| display_width(c))
+        .max()
+        .unwrap_or(0)
+        + 2;
+    let columns = (width.saturating_sub(1) / column).max(1);
+    let rows = (candidates.len() + columns - 1) / columns;
+
+    let mut menu = String::new();
+    for row in 0..rows.min(MENU_ROWS) {
+        let line: String = candidates
+            .iter()
+            .skip(row)
+            .step_by(rows)
+            .map(|c| format!("{c}{}", " ".repeat(column - display_width(c))))
+            .collect();
+        menu.push_str(line.trim_end());
+        menu.push('\n');
+    }
+    if rows > MENU_ROWS {
+        let shown = MENU_ROWS * columns;
+        menu.push_str(&format!(
+            "... and {} more\n",
+            candidates.len() - shown.min(candidates.len())
+        ));
+    }
+    menu
+}
+
+/// The most rows of completion candidates shown at once.
+const MENU_ROWS: usize = 10;
+
+/// A reverse incremental search through the history, started with Ctrl-R.
+#[derive(Debug, Default)]
+struct Search {
+    query: String,
+    /// The index of the history entry matching the query and the byte offset of the match.
+    found: Option<(usize, usize)>,
+    /// Whether the last change of the query found no (older) match.
+    failed: bool,
+}
+
+impl Search {
+    /// Applies a search key, returns whether it was one.
+    fn handle(&mut self, key: &Key, history: &[String]) -> bool {
+        match key {
+            Key::Char(c) if !c.is_control() => {
+                self.query.push(*c);
+                // the current match may still match the longer query
+                self.find(history, self.found.map_or(history.len(), |(i, _)| i + 1));
+            }
+            Key::Backspace => {
+                self.query.pop();
+                self.found = None;
+                self.find(history, history.len());
+            }
+            Key::Ctrl('r') => self.find(history, self.found.map_or(history.len(), |(i, _)| i)),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Finds the newest entry before `before` containing the query, keeping the last match if
+    /// there is none.
+    fn find(&mut self, history: &[String], before: usize) {
+        if self.query.is_empty() {
+            self.failed = false;
+            return;
+        }
+        let found = history[..before]
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(i, entry)| entry.find(&self.query).map(|offset| (i, offset)));
+        self.failed = found.is_none();
+        self.found = found.or(self.found);
+    }
+
+    /// Returns the prompt shown instead of the regular one while searching.
+    fn prompt(&self) -> String {
+        let failed = if self.failed { "failed " } else { "" };
+        format!("({failed}reverse-i-search)`{}': ", self.query)
+    }
+}
+
+/// Returns the row and column after printing `texts` from the start of a row, wrapped at
+/// `width` columns like a terminal does. A full row ends at column `width`.
+pub(super) fn position(texts: &[&str], width: usize) -> (usize, usize) {
+    let (mut row, mut col) = (0, 0);
+    for text in texts {
+        for segment in segments(text) {
+            let text = match segment {
+                Segment::Text(text) => text,
+                Segment::Escape(_) => continue,
+            };
+            for grapheme in text.graphemes(true) {
+                let w = grapheme_width(grapheme);
+                if col + w > width {
+                    (row, col) = (row + 1, 0);
+                }
+                col += w;
+            }
+        }
+    }
+    (row, col)
+}
+
+/// Checks a line, returns the error message if it's rejected.
+pub(super) type Check<'a> = dyn Fn(&str) -> Result<(), String> + 'a;
+
+/// Reads a line with the editor.
+#[derive(Default)]
+pub(super) struct LineReader<'a> {
+    /// Printed before the line, the last line of it is redrawn with the edited text.
+    pub(super) prompt: &'a str,
+    /// Recalled with Up and Down, oldest first.
+    pub(super) history: &'a [String],
+    /// Completes the text before the cursor on Tab.
+    pub(super) completer: Option<&'a dyn Completer>,
+    /// Checks the line on Enter, returns the error shown below it if it's rejected.
+    pub(super) check: Option<&'a Check<'a>>,
+    /// The text the line starts with.
+    pub(super) initial_text: &'a str,
+}
+
+impl LineReader<'_> {
+    /// Prints the prompt and reads a line until Enter is pressed on a line passing the check.
+    pub(super) fn read(&self) -> io::Result<String> {
+        let history = self.history;
+        let (head, prompt) = match self.prompt.rsplit_once('\n') {
+            Some((head, prompt)) => (Some(head), prompt),
+            None => (None, self.prompt),
+        };
+        if let Some(head) = head {
+            println!("{head}");
+        }
+
+        let _raw = RawMode::enable()?;
+        let mut editor = Editor::default();
+        editor.set(self.initial_text);
+        let mut screen = Screen::default();
+        let mut search: Option<Search> = None;
+        // the recalled history entry, the line being written when past the newest one
+        let mut recalled = history.len();
+        let mut draft = String::new();
+        // the completion candidates listed on a second Tab
+        let mut candidates: Option<Vec<String>> = None;
+        // the error of the last rejected line, shown below it
+        let mut error = String::new();
+        screen.draw(prompt, &editor.buffer, editor.cursor, &error);
+        loop {
+            let key = read_key()?;
+            if let Some(active) = &mut search {
+                if active.handle(&key, history) {
+                    let (text, cursor) = match active.found {
+                        Some((i, offset)) => (history[i].as_str(), offset),
+                        None => (editor.buffer.as_str(), editor.cursor),
+                    };
+                    screen.draw(&active.prompt(), text, cursor, &error);
+                    continue;
+                }
+                if matches!(key, Key::Escape | Key::Ctrl('g')) {
+                    search = None;
+                    screen.draw(prompt, &editor.buffer, editor.cursor, &error);
+                    continue;
+                }
+                // any other key accepts the match
+                if let Some((i, offset)) = active.found {
+                    editor.set(&history[i]);
+                    editor.cursor = offset;
+                    recalled = i;
+                }
+                search = None;
+                screen.draw(prompt, &editor.buffer, editor.cursor, &error);
+            }
+
+            let listed = candidates.take();
+            match key {
+                Key::Enter => match self.check.map_or(Ok(()), |check| check(&editor.buffer)) {
+                    Ok(()) => break,
+                    Err(message) => error = message,
+                },
+                Key::ArrowUp | Key::Ctrl('p') if recalled > 0 => {
+                    if recalled == history.len() {
+                        draft = std::mem::take(&mut editor.buffer);
+                    }
+                    recalled -= 1;
+                    editor.set(&history[recalled]);
+                }
+                Key::ArrowDown | Key::Ctrl('n') if recalled < history.len() => {
+                    recalled += 1;
+                    match history.get(recalled) {
+                        Some(entry) => editor.set(entry),
+                        None => editor.set(&draft),
+                    }
+                }
+                Key::Ctrl('r') => {
+                    let active = Search::default();
+                    screen.draw(&active.prompt(), &editor.buffer, editor.cursor, &error);
+                    search = Some(active);
+                    continue;
+                }
+                Key::Tab => {
+                    let completer = match self.completer {
+                        Some(completer) => completer,
+                        None => continue,
+                    };
+                    let (start, found) = completer.complete(&editor.buffer, editor.cursor);
+                    if start > editor.cursor || !editor.buffer.is_char_boundary(start) {
+                        continue;
+                    }
+                    let prefix = common_prefix(&found);
+                    if prefix.len() > editor.cursor - start || found.len() == 1 {
+                        editor.replace(start..editor.cursor, prefix);
+                    } else if found.len() > 1 {
+                        // a second Tab without progress lists the candidates
+                        if listed.is_some() {
+                            let width = terminal_size().map_or(usize::MAX, |(columns, _)| columns);
+                            let below = format!("{error}\n{}", menu(&found, width));
+                            let below = below.trim_start_matches('\n');
+                            screen.draw(prompt, &editor.buffer, editor.cursor, below);
+                        }
+                        candidates = Some(found);
+                        continue;
+                    } else {
+                        continue;
+                    }
+                }
+                key => {
+                    if !editor.handle(&key) {
+                        continue;
+                    }
+                }
+            }
+            screen.draw(prompt, &editor.buffer, editor.cursor, &error);
+        }
+        screen.finish(prompt, &editor.buffer);
+        Ok(editor.buffer)
+    }
+}
@@ -0,0 +1,176 @@
+//! Fuzzy Matching
+//!
+//! Scores how well a pattern matches a text, like fzf does: the characters of the pattern have to
+//! appear in the text in order, and matches at word boundaries, in camelCase humps or right after
+//! each other score higher than scattered ones.
+
+const SCORE_MATCH: i64 = 16;
+const SCORE_GAP_START: i64 = -3;
+const SCORE_GAP_EXTENSION: i64 = -1;
+const BONUS_BOUNDARY: i64 = SCORE_MATCH / 2;
+const BONUS_BOUNDARY_WHITE: i64 = BONUS_BOUNDARY + 2;
+const BONUS_BOUNDARY_DELIMITER: i64 = BONUS_BOUNDARY + 1;
+const BONUS_NON_WORD: i64 = SCORE_MATCH / 2;
+const BONUS_CAMEL: i64 = BONUS_BOUNDARY - 1;
+/// Keeps consecutive matches ahead of the same matches with a gap between them.
+const BONUS_CONSECUTIVE: i64 = -(SCORE_GAP_START + SCORE_GAP_EXTENSION);
+const BONUS_FIRST_CHAR_MULTIPLIER: i64 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Class {
+    White,
+    Delimiter,
+    NonWord,
+    Lower,
+    Upper,
+    Letter,
+    Number,
+}
+
+impl Class {
+    fn of(c: char) -> Self {
+        match c {
+            c if c.is_whitespace() => Class::White,
+            '/' | ',' | ':' | ';' | '|' | '-' | '_' | '.' => Class::Delimiter,
+            c if c.is_lowercase() => Class::Lower,
+            c if c.is_uppercase() => Class::Upper,
+            c if c.is_alphabetic() => Class::Letter,
+            c if c.is_numeric() => Class::Number,
+            _ => Class::NonWord,
+        }
+    }
+
+    fn is_word(self) -> bool {
+        !matches!(self, Class::White | Class::Delimiter | Class::NonWord)
+    }
+}
+
+/// Returns the bonus for matching a character of class `class` following one of class `prev`.
+fn bonus(prev: Class, class: Class) -> i64 {
+    if class.is_word() {
+        match prev {
+            Class::White => BONUS_BOUNDARY_WHITE,
+            Class::Delimiter => BONUS_BOUNDARY_DELIMITER,
+            Class::NonWord => BONUS_BOUNDARY,
+            Class::Lower if class == Class::Upper => BONUS_CAMEL,
+            prev if prev != Class::Number && class == Class::Number => BONUS_CAMEL,
+            _ => 0,
+        }
+    } else if class == Class::White {
+        BONUS_BOUNDARY_WHITE
+    } else {
+        BONUS_NON_WORD
+    }
+}
+
+/// Matches `pattern` against `text`, returns the score of the best match and the indices of the
+/// matched characters of `text`, or `None` if it doesn't contain the pattern's characters in
+/// order.
+///
+/// The match ignores case unless the pattern contains uppercase characters. Higher scores are
+/// better matches; an empty pattern matches everything with a score of 0.
+///
+/// # Example
+///
+/// ```
+/// use console_utils::input::fuzzy_match;
+///
+/// let (score, indices) = fuzzy_match("fb", "foo-bar").unwrap();
+/// assert_eq!(indices, [0, 4]);
+/// assert!(score > fuzzy_match("fb", "fizzbuzz").unwrap().0);
+/// assert_eq!(fuzzy_match("bf", "foo-bar"), None);
+/// ```
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<(i64, Vec<usize>)> {
+    let case_sensitive = pattern.chars().any(char::is_uppercase);
+    let fold = |c: char| {
+        if case_sensitive {
+            c
+        } else {
+            c.to_lowercase().next().unwrap_or(c)
+        }
+    };
+    let pattern: Vec<char> = pattern.chars().map(fold).collect();
+    let chars: Vec<char> = text.chars().collect();
+    if pattern.is_empty() {
+        return Some((0, Vec::new()));
+    }
+    let (n, m) = (pattern.len(), chars.len());
+    if n > m {
+        return None;
+    }
+
+    let mut prev = Class::White;
+    let bonuses: Vec<i64> = chars
+        .iter()
+        .map(|&c| {
+            let class = Class::of(c);
+            let bonus = bonus(prev, class);
+            prev = class;
+            bonus
+        })
+        .collect();
+    let chars: Vec<char> = chars.into_iter().map(fold).collect();
+
+    // score[i][j]: the best score of the pattern up to `i` with `i` matched at `j`, and the
+    // bonus of the first match of the chunk of consecutive matches ending there
+    let mut score = vec![vec![None::<(i64, i64)>; m]; n];
+    // from[i][j]: where the pattern character before `i` was matched
+    let mut from = vec![vec![0; m]; n];
+    for i in 0..n {
+        // the best score to continue with a gap before j, and where the gap starts
+        let mut gap: Option<(i64, usize)> = None;
+        for j in 0..m {
+            if chars[j] == pattern[i] {
+                let bonus = bonuses[j];
+                if i == 0 {
+                    score[i][j] = Some((SCORE_MATCH + bonus * BONUS_FIRST_CHAR_MULTIPLIER, bonus));
+                } else {
+                    let consecutive =
+                        j.checked_sub(1)
+                            .and_then(|k| score[i - 1][k])
+                            .map(|(s, chunk)| {
+                                let bonus = bonus.max(chunk).max(BONUS_CONSECUTIVE);
+                                (s + SCORE_MATCH + bonus, chunk.max(bonus))
+                            });
+                    let gapped = gap.map(|(g, k)| (g + SCORE_MATCH + bonus, k));
+                    match (consecutive, gapped) {
+                        (Some((c, chunk)), gapped) if gapped.map_or(true, |(g, _)| c >= g) => {
+                            score[i][j] = Some((c, chunk));
+                            from[i][j] = j - 1;
+                        }
+                        (_, Some((g, k))) => {
+                            score[i][j] = Some((g, bonus));
+                            from[i][j] = k;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            // a gap before j + 1 follows a match at j - 1 or extends the one before j
+            if i > 0 && j > 0 {
+                let extended = gap.map(|(g, k)| (g + SCORE_GAP_EXTENSION, k));
+                let started = score[i - 1][j - 1].map(|(s, _)| (s + SCORE_GAP_START, j - 1));
+                gap = match (extended, started) {
+                    (Some(e), Some(s)) => Some(if e.0 >= s.0 { e } else { s }),
+                    (e, s) => e.or(s),
+                };
+            }
+        }
+    }
+
+    let (mut j, best) = score[n - 1]
+        .iter()
+        .enumerate()
+        .filter_map(|(j, s)| s.map(|(s, _)| (j, s)))
+        .fold(None, |best: Option<(usize, i64)>, (j, s)| match best {
+            Some((_, b)) if b >= s => best,
+            _ => Some((j, s)),
+        })?;
+    let mut indices = vec![0; n];
+    for i in (0..n).rev() {
+        indices[i] = j;
+        j = from[i][j];
+    }
+    Some((best, indices))
+}
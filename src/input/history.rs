@@ -0,0 +1,206 @@
+//! Input History
+//!
+//! Previously entered lines, recalled in the line editor with Up and Down or searched with
+//! Ctrl-R. Histories can be kept separately per prompt in namespaces, and persisted to a file.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// Previously entered lines of [`Input`](super::Input) prompts, oldest first.
+///
+/// Entries are kept in the default namespace unless a prompt uses a
+/// [`history_id`](super::Input::history_id), so unrelated prompts don't share their answers.
+///
+/// # Example
+///
+/// ```no_run
+/// use console_utils::input::{History, Input};
+///
+/// let mut history = History::load(".tool_history").unwrap().max_size(500);
+/// loop {
+///     let command: String = Input::new("Command").history(&mut history).read();
+///     let port: u16 = Input::new("Port")
+///         .history(&mut history)
+///         .history_id("port")
+///         .read();
+///     history.save(".tool_history").unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct History {
+    entries: BTreeMap<String, Vec<String>>,
+    max_size: usize,
+    dedup: bool,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            max_size: 1000,
+            dedup: true,
+        }
+    }
+}
+
+impl History {
+    /// Creates an empty history keeping up to 1000 entries per namespace, without duplicates.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of entries per namespace, dropping the oldest ones beyond it.
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        for entries in self.entries.values_mut() {
+            truncate(entries, max_size);
+        }
+        self
+    }
+
+    /// Sets whether an entry added again replaces its earlier occurrences, enabled by default.
+    /// Otherwise only immediate repetitions are skipped.
+    pub fn dedup(mut self, dedup: bool) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
+    /// Returns the entries of the default namespace, oldest first.
+    pub fn entries(&self) -> &[String] {
+        self.entries_in("")
+    }
+
+    /// Returns the entries of the namespace `id`, oldest first.
+    pub fn entries_in(&self, id: &str) -> &[String] {
+        self.entries.get(id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Adds an entry to the default namespace. Blank entries are ignored.
+    pub fn push(&mut self, entry: &str) {
+        self.push_in("", entry);
+    }
+
+    /// Adds an entry to the namespace `id`. Blank entries are ignored.
+    pub fn push_in(&mut self, id: &str, entry: &str) {
+        if entry.trim().is_empty() || self.max_size == 0 {
+            return;
+        }
+        let entries = self.entries.entry(clean_id(id)).or_default();
+        if self.dedup {
+            entries.retain(|e| e != entry);
+        } else if entries.last().map_or(false, |last| last == entry) {
+            return;
+        }
+        entries.push(entry.to_string());
+        truncate(entries, self.max_size);
+    }
+
+    /// Removes all entries of all namespaces.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Loads a history saved with [`save`](History::save), or starts an empty one if the file
+    /// doesn't exist yet.
+    ///
+    /// The file has one entry per line, prefixed with its namespace and a tab outside of the
+    /// default namespace. Tabs, line breaks and backslashes in entries are escaped with a
+    /// backslash.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut history = Self::new();
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(history),
+            Err(e) => return Err(e),
+        };
+        for line in text.lines() {
+            let (id, entry) = line.split_once('\t').unwrap_or(("", line));
+            history.push_in(id, &unescape(entry));
+        }
+        Ok(history)
+    }
+
+    /// Saves the history to a file, replacing it atomically, so it's never left half-written.
+    ///
+    /// Histories saved to the same file by several programs aren't merged: the file holds the
+    /// one saved last. To keep the entries of the others, [`load`](History::load) the file and
+    /// push the new entries again right before saving.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut text = String::new();
+        for (id, entries) in &self.entries {
+            for entry in entries {
+                if !id.is_empty() {
+                    text.push_str(id);
+                    text.push('\t');
+                }
+                text.push_str(&escape(entry));
+                text.push('\n');
+            }
+        }
+
+        let mut temp = PathBuf::from(path).into_os_string();
+        temp.push(format!(".{}.tmp", std::process::id()));
+        let result = fs::File::create(&temp)
+            .and_then(|mut file| {
+                file.write_all(text.as_bytes())?;
+                file.sync_all()
+            })
+            .and_then(|_| fs::rename(&temp, path));
+        if result.is_err() {
+            let _ = fs::remove_file(&temp);
+        }
+        result
+    }
+}
+
+/// Drops the oldest entries beyond `max_size`.
+fn truncate(entries: &mut Vec<String>, max_size: usize) {
+    if entries.len() > max_size {
+        entries.drain(..entries.len() - max_size);
+    }
+}
+
+/// Removes the characters separating namespaces from entries in the file.
+fn clean_id(id: &str) -> String {
+    id.chars()
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+        .collect()
+}
+
+fn escape(entry: &str) -> String {
+    let mut escaped = String::with_capacity(entry.len());
+    for c in entry.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(entry: &str) -> String {
+    let mut unescaped = String::with_capacity(entry.len());
+    let mut chars = entry.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(c) => unescaped.push(c),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
@@ -0,0 +1,1057 @@
+//! Input Utilities
+//!
+//! This module provides functions for handling user input in console applications, including reading user input,
+//! selecting options from a list, displaying spinners, and gradually revealing, skippable strings.
+
+use std::{
+    fmt::{Debug, Display},
+    io,
+    marker::PhantomData,
+    str::FromStr,
+    thread,
+    time::{Duration, Instant},
+};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+mod completion;
+mod editor;
+mod fuzzy;
+mod history;
+mod password;
+
+pub use completion::{Completer, PathCompleter, WordCompleter};
+use editor::{position, LineReader, Screen};
+pub use fuzzy::fuzzy_match;
+pub use history::History;
+pub use password::{password, Password, Secret};
+
+use crate::{
+    control::{
+        clear_below, clear_line, flush, is_terminal, move_cursor_down, move_cursor_up,
+        terminal_size, Visibility,
+    },
+    read::{key_pressed_within, read_key, Key, RawMode},
+    styled::{display_width, segments, wrap, Segment, Style, StyledString, StyledText, Wrap},
+    theme::{theme, Theme},
+};
+
+/// A Wrapper for allowing empty inputs which then return `None`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Empty<T>(pub Option<T>);
+
+impl<T> FromStr for Empty<T>
+where
+    T: FromStr,
+    T::Err: std::fmt::Debug,
+{
+    type Err = T::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().is_empty() {
+            Ok(Empty(None))
+        } else {
+            s.trim().parse::<T>().map(|v| Empty(Some(v)))
+        }
+    }
+}
+
+/// Reads user input from the console.
+///
+/// This function prompts the user with a message (`before`) and reads a line of input from the
+/// console. The input can be empty. The prompt is drawn with the current [`theme`]. For more
+/// options, like a [`History`], use an [`Input`].
+///
+/// In a terminal, the line can be edited with the cursor keys and the usual readline shortcuts:
+/// Home/End or Ctrl-A/Ctrl-E, Alt-B/Alt-F to move by word, Ctrl-W to delete the word before the
+/// cursor, Ctrl-U/Ctrl-K to delete before or after it, and Insert to toggle overwriting.
+///
+/// # Arguments
+///
+/// * `before` - The text to display before prompting for input. Add here `\n` for a new line.
+///
+/// # Returns
+///
+/// Returns an `T` containing the user's input converted to the specified type.
+///
+/// # Panics
+///
+/// Panics if the console can't be read, e.g. because the input was closed.
+pub fn input<T>(before: &str) -> T
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    Input::new(before).read()
+}
+
+/// Returns the value read by a prompt, panicking if the console couldn't be read.
+fn expect_read<T>(result: io::Result<T>) -> T {
+    result.unwrap_or_else(|e| panic!("failed to read from the console: {e}"))
+}
+
+/// Checks a value read by an [`Input`], returns the error message if it's rejected.
+type Validate<'a, T> = dyn Fn(&T) -> Result<(), String> + 'a;
+
+/// A prompt reading a line of input from the console, converted to `T`.
+///
+/// Like [`input`], but configurable before reading with [`read`](Input::read).
+///
+/// # Example
+///
+/// ```no_run
+/// use console_utils::input::{History, Input};
+///
+/// let mut history = History::new();
+/// let name: String = Input::new("Name").history(&mut history).read();
+/// ```
+pub struct Input<'a, T> {
+    before: &'a str,
+    history: Option<&'a mut History>,
+    history_id: &'a str,
+    completer: Option<Box<dyn Completer + 'a>>,
+    validate: Option<Box<Validate<'a, T>>>,
+    /// The default value and how it's shown.
+    default: Option<(T, String)>,
+    initial_text: &'a str,
+    theme: Option<&'a Theme>,
+    value: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> Input<'a, T>
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    /// Creates a prompt displaying `before`. Add here `\n` for a new line.
+    pub fn new(before: &'a str) -> Self {
+        Self {
+            before,
+            history: None,
+            history_id: "",
+            completer: None,
+            validate: None,
+            default: None,
+            initial_text: "",
+            theme: None,
+            value: PhantomData,
+        }
+    }
+
+    /// Sets the history recalled with Up and Down or searched with Ctrl-R while editing, which
+    /// valid answers are added to.
+    pub fn history(mut self, history: &'a mut History) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Sets the namespace of the history used by this prompt, the default namespace otherwise.
+    pub fn history_id(mut self, id: &'a str) -> Self {
+        self.history_id = id;
+        self
+    }
+
+    /// Sets the completer suggesting completions of the text before the cursor on Tab.
+    pub fn completer(mut self, completer: impl Completer + 'a) -> Self {
+        self.completer = Some(Box::new(completer));
+        self
+    }
+
+    /// Sets a check of the converted value, rejecting it with the returned error message.
+    ///
+    /// In a terminal, the error is shown below the prompt until the user enters a valid value.
+    /// Values that can't be converted to `T` are rejected with the `Debug` text of their error.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use console_utils::input::Input;
+    ///
+    /// let port: u16 = Input::new("Port")
+    ///     .validate(|v: &u16| (1024..=65535).contains(v).then_some(()).ok_or("must be 1024-65535"))
+    ///     .read();
+    /// ```
+    pub fn validate<F, E>(mut self, validate: F) -> Self
+    where
+        F: Fn(&T) -> Result<(), E> + 'a,
+        E: Display,
+    {
+        self.validate = Some(Box::new(move |value| {
+            validate(value).map_err(|e| e.to_string())
+        }));
+        self
+    }
+
+    /// Sets the value returned when the input is left empty, shown dimmed after the question.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use console_utils::input::Input;
+    ///
+    /// // ? Port (8080) ›
+    /// let port: u16 = Input::new("Port").default(8080).read();
+    /// ```
+    pub fn default(mut self, value: T) -> Self
+    where
+        T: Display,
+    {
+        let shown = value.to_string();
+        self.default = Some((value, shown));
+        self
+    }
+
+    /// Sets the text the line editor starts with, which the user can edit or accept as is.
+    /// Ignored when the input doesn't come from a terminal.
+    pub fn initial_text(mut self, text: &'a str) -> Self {
+        self.initial_text = text;
+        self
+    }
+
+    /// Sets the theme the prompt is drawn with, instead of the current [`theme`].
+    pub fn theme(mut self, theme: &'a Theme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// Converts and validates a line, returns the error message if it's rejected. An empty line
+    /// is accepted as `None` if there is a default value.
+    fn check(&self, line: &str) -> Result<Option<T>, String> {
+        if self.default.is_some() && line.trim().is_empty() {
+            return Ok(None);
+        }
+        let value = line.parse::<T>().map_err(|e| format!("{e:?}"))?;
+        if let Some(validate) = &self.validate {
+            validate(&value)?;
+        }
+        Ok(Some(value))
+    }
+
+    /// Prompts until the user enters a valid `T`, and returns it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the console can't be read, see [`try_read`](Input::try_read).
+    pub fn read(self) -> T {
+        expect_read(self.try_read())
+    }
+
+    /// Prompts until the user enters a valid `T`, and returns it, or the error reading the
+    /// console, e.g. because the input was closed.
+    ///
+    /// If the input ends before a valid line, the error has the kind
+    /// [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) and the message of the last rejected line.
+    pub fn try_read(mut self) -> io::Result<T> {
+        let theme = self.theme.cloned().unwrap_or_else(theme);
+        let hint = self.default.as_ref().map(|(_, shown)| shown.as_str());
+        let prompt = format!("{} ", question(&theme, self.before, hint));
+        let error = |message: &str| {
+            format!(
+                "{} {}",
+                theme.error_prefix,
+                theme.error_style.paint(message)
+            )
+        };
+
+        let (line, value) = if is_terminal() {
+            let check = |line: &str| self.check(line).map(drop).map_err(|e| error(&e));
+            let reader = LineReader {
+                prompt: &prompt,
+                history: match &self.history {
+                    Some(history) => history.entries_in(self.history_id),
+                    None => &[],
+                },
+                completer: self.completer.as_deref(),
+                check: Some(&check),
+                initial_text: self.initial_text,
+            };
+            let line = reader.read()?;
+            let value = self.check(&line);
+            (line, value)
+        } else {
+            let mut rejected = None;
+            loop {
+                print!("{prompt}");
+                flush();
+                let mut line = String::new();
+                let read = io::stdin().read_line(&mut line)?;
+                line.truncate(line.trim_end_matches(['\r', '\n']).len());
+                match self.check(&line) {
+                    Ok(value) => break (line, Ok(value)),
+                    // at the end of the input, nothing could correct the line anymore
+                    Err(e) if read == 0 => {
+                        let message = rejected.unwrap_or(e);
+                        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, message));
+                    }
+                    Err(e) => {
+                        println!("\n{}\n", error(&e));
+                        rejected = Some(e);
+                    }
+                }
+            }
+        };
+
+        if let Some(history) = &mut self.history {
+            history.push_in(self.history_id, &line);
+        }
+        match (value, self.default) {
+            (Ok(Some(value)), _) => Ok(value),
+            (Ok(None), Some((default, _))) => Ok(default),
+            _ => unreachable!("the line reader only returns valid lines"),
+        }
+    }
+}
+
+/// Asks the user a yes or no question.
+///
+/// The question is answered with a single press of `y` or `n`, in any case, or Enter for the
+/// `default` answer, shown as `(Y/n)` or `(y/N)` after the question. Without a default, Enter is
+/// ignored. The answer is then shown as a styled "Yes" or "No" in place of the hint.
+///
+/// # Arguments
+///
+/// * `before` - The question to display.
+/// * `default` - The answer returned on Enter, if any.
+///
+/// # Returns
+///
+/// Returns whether the user answered yes.
+///
+/// # Panics
+///
+/// Panics if the console can't be read, or if the input is closed before an answer and there is
+/// no `default`. With a default, a closed input answers with it.
+pub fn confirm(before: &str, default: Option<bool>) -> bool {
+    let theme = theme();
+    let hint = match default {
+        Some(true) => "Y/n",
+        Some(false) => "y/N",
+        None => "y/n",
+    };
+    let prompt = format!("{} ", question(&theme, before, Some(hint)));
+
+    if !is_terminal() {
+        loop {
+            print!("{prompt}");
+            flush();
+            let mut line = String::new();
+            let read = expect_read(io::stdin().read_line(&mut line));
+            match (line.trim().to_lowercase().as_str(), default) {
+                ("y" | "yes", _) => return true,
+                ("n" | "no", _) => return false,
+                ("", Some(default)) => return default,
+                _ if read == 0 => panic!("the input was closed without an answer to {before:?}"),
+                _ => println!(
+                    "\n{} {}\n",
+                    theme.error_prefix,
+                    theme.error_style.paint("Answer with y or n")
+                ),
+            }
+        }
+    }
+
+    let (head, prompt) = match prompt.rsplit_once('\n') {
+        Some((head, prompt)) => (Some(head), prompt),
+        None => (None, prompt.as_str()),
+    };
+    if let Some(head) = head {
+        println!("{head}");
+    }
+    let _raw = expect_read(RawMode::enable());
+    let mut screen = Screen::default();
+    screen.draw(prompt, "", 0, "");
+    let answer = loop {
+        match expect_read(read_key()) {
+            Key::Char('y' | 'Y') => break true,
+            Key::Char('n' | 'N') => break false,
+            Key::Enter if default.is_some() => break default == Some(true),
+            _ => {}
+        }
+    };
+
+    // the question without the hint, unless that would wrap differently
+    let answered = format!("{} ", question(&theme, before, None));
+    let prompt = match answered.rsplit_once('\n') {
+        Some((answered_head, answered)) if Some(answered_head) == head => answered,
+        None if head.is_none() => &answered,
+        _ => prompt,
+    };
+    let text = theme
+        .active_item_style
+        .paint(if answer { "Yes" } else { "No" })
+        .to_string();
+    screen.draw(prompt, &text, text.len(), "");
+    screen.finish(prompt, &text);
+    answer
+}
+
+/// Allows the user to select one option from a list using the console.
+///
+/// This function displays a list of options. The user can navigate through the
+/// options using arrow keys or Ctrl-P and Ctrl-N, and narrow them down by typing, which
+/// filters them with [`fuzzy_match`]. If the user presses Enter, the
+/// function returns the selected option. Lists taller than the terminal scroll, see
+/// [`Select`] to change the number of options shown at once.
+///
+/// # Arguments
+///
+/// * `before` - The text to display before the list of options.
+/// * `options` - A vector of strings representing the available options.
+///
+/// # Returns
+///
+/// Returns an `usize` as an index of the inputted array `options`, or `0` without prompting if
+/// `options` is empty.
+///
+/// # Panics
+///
+/// Panics if the console can't be read, e.g. because the input was closed.
+pub fn select<'a>(before: &'a str, options: &'a [&'a str]) -> usize {
+    if options.is_empty() {
+        return 0;
+    }
+    Select::with_label(before, 0..options.len(), |&i| options[i].to_string()).read()
+}
+
+/// Labels an item of a [`Select`].
+type Label<'a, T> = dyn Fn(&T) -> String + 'a;
+
+/// A prompt selecting one item from a list, like [`select`], returning the item itself.
+///
+/// Items are shown with their `Display` implementation, or a label function given to
+/// [`with_label`](Select::with_label).
+///
+/// # Example
+///
+/// ```no_run
+/// use console_utils::input::Select;
+///
+/// let port: u16 = Select::new("Port", 8000..8200).page_size(5).read();
+///
+/// struct Host {
+///     name: &'static str,
+///     address: &'static str,
+/// }
+/// let hosts = vec![
+///     Host { name: "web", address: "10.0.0.1" },
+///     Host { name: "db", address: "10.0.0.2" },
+/// ];
+/// // borrowed items are returned as references
+/// let host: &Host = Select::with_label("Host", &hosts, |host| {
+///     format!("{} ({})", host.name, host.address)
+/// })
+/// .read();
+/// ```
+pub struct Select<'a, T> {
+    before: &'a str,
+    items: Vec<T>,
+    label: Box<Label<'a, T>>,
+    page_size: Option<usize>,
+    theme: Option<&'a Theme>,
+}
+
+impl<'a, T> Select<'a, T> {
+    /// Creates a prompt displaying `before` above the `items`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no `items`, as none could be selected.
+    pub fn new(before: &'a str, items: impl IntoIterator<Item = T>) -> Self
+    where
+        T: Display + 'a,
+    {
+        Self::with_label(before, items, |item: &T| item.to_string())
+    }
+
+    /// Creates a prompt displaying `before` above the `items`, labelled by `label`. The items
+    /// don't have to implement `Display`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no `items`, as none could be selected.
+    pub fn with_label(
+        before: &'a str,
+        items: impl IntoIterator<Item = T>,
+        label: impl Fn(&T) -> String + 'a,
+    ) -> Self {
+        let items: Vec<T> = items.into_iter().collect();
+        assert!(!items.is_empty(), "a Select needs at least one item");
+        Self {
+            before,
+            items,
+            label: Box::new(label),
+            page_size: None,
+            theme: None,
+        }
+    }
+
+    /// Sets the number of options shown at once, scrolling through the rest. Defaults to what
+    /// fits in the terminal.
+    pub fn page_size(mut self, page_size: usize) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// Sets the theme the prompt is drawn with, instead of the current [`theme`].
+    pub fn theme(mut self, theme: &'a Theme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// Shows the items until the user presses Enter, and returns the selected one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the console can't be read, see [`try_read`](Select::try_read).
+    pub fn read(self) -> T {
+        expect_read(self.try_read())
+    }
+
+    /// Shows the items until the user presses Enter, and returns the selected one, or the error
+    /// reading the console, e.g. because the input was closed.
+    pub fn try_read(mut self) -> io::Result<T> {
+        let labels: Vec<String> = self.items.iter().map(&self.label).collect();
+        let options: &[&str] = &labels.iter().map(String::as_str).collect::<Vec<_>>();
+        let mut i = 0;
+        let mut scroll = Scroll::new(options.len(), self.page_size);
+        let theme = self.theme.cloned().unwrap_or_else(theme);
+        let question = question(&theme, self.before, None);
+        let mut query = String::new();
+        let mut matches = filter(options, &query);
+
+        // hide cursor
+        let vis = Visibility::new();
+        vis.hide_cursor();
+
+        // print everything, the question with the filter above the options
+        let draw =
+            |rows: usize, query: &str, matches: &[(usize, Vec<usize>)], i, scroll: &Scroll| {
+                move_cursor_up(rows);
+                clear_below();
+                let mut rows = 0;
+                for line in format!("{question} {query}").split('\n') {
+                    println!("{line}");
+                    rows += position(&[line], terminal_width()).0 + 1;
+                }
+                let labels: Vec<&str> = matches.iter().map(|(index, _)| options[*index]).collect();
+                let matched: Vec<Vec<usize>> = matches.iter().map(|(_, m)| m.clone()).collect();
+                let lines = populate(&theme, &labels, &matched, None, i, scroll);
+                (rows, lines)
+            };
+        let (mut rows, mut lines) = draw(0, &query, &matches, i, &scroll);
+
+        loop {
+            match read_key()? {
+                Key::Enter if !matches.is_empty() => {
+                    break;
+                }
+                Key::Char(c) if !c.is_control() => query.push(c),
+                Key::Backspace if !query.is_empty() => {
+                    query.pop();
+                }
+                Key::Ctrl('u') if !query.is_empty() => query.clear(),
+                key => {
+                    if scroll.navigate(&key, &mut i) {
+                        (rows, lines) = draw(rows, &query, &matches, i, &scroll);
+                    }
+                    continue;
+                }
+            }
+            matches = filter(options, &query);
+            i = 0;
+            scroll = Scroll::new(matches.len(), Some(scroll.size));
+            (rows, lines) = draw(rows, &query, &matches, i, &scroll);
+        }
+
+        // reset cursor
+        move_cursor_down(lines);
+
+        Ok(self.items.swap_remove(matches[i].0))
+    }
+}
+
+/// Returns the indices of the `options` matching `query` with the indices of their matched
+/// characters, best matches first.
+fn filter(options: &[&str], query: &str) -> Vec<(usize, Vec<usize>)> {
+    let mut matches: Vec<(i64, usize, Vec<usize>)> = options
+        .iter()
+        .enumerate()
+        .filter_map(|(i, option)| fuzzy_match(query, option).map(|(score, m)| (score, i, m)))
+        .collect();
+    matches.sort_by_key(|(score, _, _)| std::cmp::Reverse(*score));
+    matches.into_iter().map(|(_, i, m)| (i, m)).collect()
+}
+
+/// Allows the user to select multiple options from a list using the console.
+///
+/// This function displays a list of options with checkboxes. The user can navigate through the
+/// options using arrow keys or 'w' and 's' keys. Pressing the spacebar toggles the selection of
+/// the current option. If the user presses Enter, the function returns a vector of booleans
+/// indicating which options were selected. Lists taller than the terminal scroll, see
+/// [`MultiSelect`] to change the number of options shown at once.
+///
+/// # Arguments
+///
+/// * `before` - The text to display before the list of options.
+/// * `options` - A vector of strings representing the available options.
+///
+/// # Returns
+///
+/// Returns an `Vec<bool>` containing a vector of booleans indicating which options were
+/// selected.
+///
+/// # Panics
+///
+/// Panics if the console can't be read, e.g. because the input was closed.
+pub fn multiselect(before: &str, options: &[&str]) -> Vec<bool> {
+    MultiSelect::new(before, options).read()
+}
+
+/// A prompt selecting multiple options from a list, like [`multiselect`].
+#[derive(Debug, Clone)]
+pub struct MultiSelect<'a> {
+    before: &'a str,
+    options: &'a [&'a str],
+    page_size: Option<usize>,
+    theme: Option<&'a Theme>,
+}
+
+impl<'a> MultiSelect<'a> {
+    /// Creates a prompt displaying `before` above the `options`.
+    pub fn new(before: &'a str, options: &'a [&'a str]) -> Self {
+        Self {
+            before,
+            options,
+            page_size: None,
+            theme: None,
+        }
+    }
+
+    /// Sets the number of options shown at once, scrolling through the rest. Defaults to what
+    /// fits in the terminal.
+    pub fn page_size(mut self, page_size: usize) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// Sets the theme the prompt is drawn with, instead of the current [`theme`].
+    pub fn theme(mut self, theme: &'a Theme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// Shows the options until the user presses Enter, and returns which ones were selected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the console can't be read, see [`try_read`](MultiSelect::try_read).
+    pub fn read(self) -> Vec<bool> {
+        expect_read(self.try_read())
+    }
+
+    /// Shows the options until the user presses Enter, and returns which ones were selected, or
+    /// the error reading the console, e.g. because the input was closed.
+    pub fn try_read(self) -> io::Result<Vec<bool>> {
+        let options = self.options;
+        let mut matrix: Vec<bool> = vec![false; options.len()];
+        let mut i = 0;
+        let mut scroll = Scroll::new(options.len(), self.page_size);
+        let theme = self.theme.cloned().unwrap_or_else(theme);
+
+        // print everything
+        println!("{} ", question(&theme, self.before, None));
+
+        let mut lines = populate(&theme, options, &[], Some(&matrix), 0, &scroll);
+
+        // hide cursor
+        let vis = Visibility::new();
+        vis.hide_cursor();
+
+        loop {
+            match read_key()? {
+                Key::Char(' ') => {
+                    matrix[i] = !matrix[i];
+                    lines = populate(&theme, options, &[], Some(&matrix), i, &scroll);
+                }
+                Key::Enter => {
+                    break;
+                }
+                key => {
+                    let key = match key {
+                        Key::Char('w') | Key::Char('W') => Key::ArrowUp,
+                        Key::Char('s') | Key::Char('S') => Key::ArrowDown,
+                        key => key,
+                    };
+                    if scroll.navigate(&key, &mut i) {
+                        lines = populate(&theme, options, &[], Some(&matrix), i, &scroll);
+                    }
+                }
+            }
+        }
+
+        // reset cursor
+        move_cursor_down(lines);
+
+        Ok(matrix)
+    }
+}
+
+/// The rows of the terminal kept free of options by default: the question, the scroll
+/// indicators and the line of the cursor.
+const PAGE_MARGIN: usize = 4;
+
+/// The options of a list shown at once, scrolled to keep the cursor in view.
+#[derive(Debug, Clone, Copy)]
+struct Scroll {
+    /// The number of options.
+    len: usize,
+    /// The number of options shown at once.
+    size: usize,
+    /// The first option shown.
+    offset: usize,
+}
+
+impl Scroll {
+    /// Creates a scroll for `len` options, showing `size` or what fits in the terminal.
+    fn new(len: usize, size: Option<usize>) -> Self {
+        let size = size.unwrap_or_else(|| {
+            terminal_size().map_or(len, |(_, rows)| rows.saturating_sub(PAGE_MARGIN))
+        });
+        Self {
+            len,
+            size: size.max(1),
+            offset: 0,
+        }
+    }
+
+    /// Whether not all options fit.
+    fn overflows(&self) -> bool {
+        self.len > self.size
+    }
+
+    /// Applies a navigation key to the `cursor`, scrolling it into view. Returns whether it was
+    /// one.
+    fn navigate(&mut self, key: &Key, cursor: &mut usize) -> bool {
+        let last = self.len.saturating_sub(1);
+        *cursor = match key {
+            Key::ArrowUp | Key::Ctrl('p') if *cursor > 0 => *cursor - 1,
+            Key::ArrowDown | Key::Ctrl('n') if *cursor < last => *cursor + 1,
+            Key::PageUp => cursor.saturating_sub(self.size),
+            Key::PageDown => (*cursor + self.size).min(last),
+            Key::Home => 0,
+            Key::End => last,
+            _ => return false,
+        };
+        if *cursor < self.offset {
+            self.offset = *cursor;
+        } else if *cursor >= self.offset + self.size {
+            self.offset = *cursor + 1 - self.size;
+        }
+        true
+    }
+}
+
+/// Returns the number of columns of the terminal, without a limit if unknown.
+fn terminal_width() -> usize {
+    terminal_size().map_or(usize::MAX, |(columns, _)| columns)
+}
+
+/// Formats the question of a prompt, from the prompt prefix to the suffix, wrapped to the width
+/// of the terminal. A `hint` is shown in parentheses after the question.
+fn question(theme: &Theme, before: &str, hint: Option<&str>) -> String {
+    let hint = hint.map_or(String::new(), |hint| {
+        format!(" {}", theme.hint_style.paint(&format!("({hint})")))
+    });
+    let question = format!(
+        "{} {}{hint} {}",
+        theme.prompt_prefix,
+        theme.prompt_style.paint(before),
+        theme.prompt_suffix
+    );
+    let hanging = " ".repeat(display_width(theme.prompt_prefix.text()) + 1);
+    Wrap::new(terminal_width().saturating_sub(1))
+        .hanging_indent(&hanging)
+        .wrap(&question)
+}
+
+/// Formats `glyph`, padded with spaces to `width` terminal cells.
+fn pad(glyph: &StyledText, width: usize) -> String {
+    let padding = width.saturating_sub(display_width(glyph.text()));
+    format!("{glyph}{}", " ".repeat(padding))
+}
+
+/// Populate function for select/multiselect
+///
+/// Options too long for the terminal are wrapped, only the ones in view of the `scroll` are
+/// printed. The characters at the indices in `matched` are highlighted. Returns the number of
+/// printed lines.
+fn populate(
+    theme: &Theme,
+    options: &[&str],
+    matched: &[Vec<usize>],
+    matrix: Option<&[bool]>,
+    cursor: usize,
+    scroll: &Scroll,
+) -> usize {
+    let cursor_width = display_width(theme.active_item_prefix.text())
+        .max(display_width(theme.inactive_item_prefix.text()));
+    let check_width = display_width(theme.checked_item_prefix.text())
+        .max(display_width(theme.unchecked_item_prefix.text()));
+    let hanging = match (matrix, check_width) {
+        (Some(_), 1..) => " ".repeat(cursor_width + check_width + 3),
+        _ => " ".repeat(cursor_width + 2),
+    };
+    let wrap = Wrap::new(terminal_width().saturating_sub(1)).hanging_indent(&hanging);
+
+    let visible = scroll.offset..(scroll.offset + scroll.size).min(options.len());
+    let indicator = |glyph: &StyledText, hidden: usize| {
+        if hidden == 0 {
+            String::new()
+        } else {
+            let more = format!("{hidden} more");
+            format!(
+                " {} {}",
+                pad(glyph, cursor_width),
+                theme.hint_style.paint(&more)
+            )
+        }
+    };
+
+    clear_below();
+    let mut lines = 0;
+    if scroll.overflows() {
+        println!("{}", indicator(&theme.scroll_up_indicator, visible.start));
+        lines += 1;
+    }
+    for (i, option) in options
+        .iter()
+        .enumerate()
+        .take(visible.end)
+        .skip(visible.start)
+    {
+        let checked = matrix.map_or(false, |m| m[i]);
+        let (prefix, style) = if i == cursor {
+            (&theme.active_item_prefix, theme.active_item_style)
+        } else {
+            (&theme.inactive_item_prefix, theme.item_style)
+        };
+        let style = if checked {
+            theme.checked_item_style
+        } else {
+            style
+        };
+        let check = match (matrix, checked) {
+            (Some(_), _) if check_width == 0 => String::new(),
+            (Some(_), true) => format!("{} ", pad(&theme.checked_item_prefix, check_width)),
+            (Some(_), false) => format!("{} ", pad(&theme.unchecked_item_prefix, check_width)),
+            (None, _) => String::new(),
+        };
+        let option = highlight(
+            theme,
+            option,
+            matched.get(i).map_or(&[], Vec::as_slice),
+            style,
+        );
+        let line = format!(" {} {check}{option}", pad(prefix, cursor_width));
+        for line in wrap.wrap(&line).split('\n') {
+            println!("{line}");
+            lines += 1;
+        }
+    }
+    if scroll.overflows() {
+        let hidden = options.len() - visible.end;
+        println!("{}", indicator(&theme.scroll_down_indicator, hidden));
+        lines += 1;
+    }
+    move_cursor_up(lines);
+    lines
+}
+
+/// Paints `option` with `style`, patched with the match style of the theme at the `matched`
+/// character indices.
+fn highlight(theme: &Theme, option: &str, matched: &[usize], style: Style) -> String {
+    let mut text = StyledString::new();
+    let mut matched = matched.iter().peekable();
+    for (i, c) in option.chars().enumerate() {
+        let style = if matched.next_if_eq(&&i).is_some() {
+            style.patch(theme.match_style)
+        } else {
+            style
+        };
+        text.push_str(c.encode_utf8(&mut [0; 4]), style);
+    }
+    text.to_string()
+}
+
+/// Enumeration representing different types of spinners.
+#[derive(Debug, Clone)]
+pub enum SpinnerType {
+    /// Spinner with characters `/` `-` `\` `|`.
+    Standard,
+    /// Spinner with dots `.` `..` `...` `.....`.
+    Dots,
+    /// Spinner with box characters `▌` `▀` `▐` `▄`.
+    Box,
+    /// Spinner with flip characters `_` `_` `_` `-` `\` `'` `´` `-` `_` `_` `_`.
+    Flip,
+    /// Custom spinner with user-defined frames.
+    Custom(&'static [&'static str]),
+}
+
+impl SpinnerType {
+    /// Returns the frames of the spinner type.
+    pub fn frames(&self) -> &'static [&'static str] {
+        match self {
+            SpinnerType::Standard => &["/", "-", "\\", "|"],
+            SpinnerType::Dots => &[".", "..", "...", "....", "...", ".."],
+            SpinnerType::Box => &["▌", "▀", "▐", "▄"],
+            SpinnerType::Flip => &["_", "_", "_", "-", "`", "`", "'", "´", "-", "_", "_", "_"],
+            SpinnerType::Custom(frames) => frames,
+        }
+    }
+}
+
+/// Displays a console-based spinner animation.
+///
+/// A spinner is a visual indicator of a long-running process. It consists of a set of frames
+/// that are displayed sequentially to create the appearance of motion.
+///
+/// # Parameters
+///
+/// - `time`: A floating-point number representing the duration of the spinner animation in seconds.
+/// - `spinner_type`: The type of spinner to display.
+pub fn spinner(mut time: f64, spinner_type: SpinnerType) {
+    let frames = spinner_type.frames();
+    let mut i = 0;
+
+    while time > 0.0 {
+        clear_line();
+        print!("{}", frames[i]);
+        flush();
+        thread::sleep(Duration::from_secs_f64(0.075));
+        time -= 0.075;
+        if i < frames.len() - 1 {
+            i += 1
+        } else {
+            i = 0
+        }
+    }
+
+    clear_line();
+}
+
+const FAST_GRACE_MS: u64 = 120;
+const FAST_GRACE: Duration = Duration::from_millis(FAST_GRACE_MS);
+
+/// Splits `str` into the units revealed one at a time: graphemes together with the escape
+/// sequences preceding them, so styled text like a [`Gradient`](crate::styled::Gradient) is
+/// revealed without delays between its escape codes.
+fn reveal_units(str: &str) -> Vec<String> {
+    let mut units = Vec::new();
+    let mut pending = String::new();
+    for segment in segments(str) {
+        match segment {
+            Segment::Escape(escape) => pending.push_str(escape),
+            Segment::Text(text) => {
+                for grapheme in text.graphemes(true) {
+                    pending.push_str(grapheme);
+                    units.push(std::mem::take(&mut pending));
+                }
+            }
+        }
+    }
+    match units.last_mut() {
+        Some(last) => last.push_str(&pending),
+        None if !pending.is_empty() => units.push(pending),
+        None => {}
+    }
+    units
+}
+
+/// Reveals a string gradually, printing one character at a time with a specified time interval.
+///
+/// Useful for typing effects or slow reveals. Can be sped up with the optional skip key and time.
+/// Styled text can be revealed as well, its escape sequences are printed without delay. Lines
+/// longer than the terminal are wrapped between words, so words aren't split while revealing.
+///
+/// # Arguments
+///
+/// - `str` - The string to reveal gradually. Include `\n` for new lines.
+/// - `time_between` - The time interval (in seconds) between each revealed character.
+/// - `skip` - If `Some((key, faster_time_between))`, pressing this `key` will temporarily speed up
+///   the reveal rate by the `faster_time_between`. The speed-up lasts briefly after the last press (a grace period of 120
+///   milliseconds) before returning to the normal pace. Holding or repeatedly pressing the key will
+///   extend the fast-forward window. If `None`, the reveal speed cannot be changed.
+pub fn reveal(str: &str, time_between: f64, skip: Option<(Key, f64)>) {
+    // Sanitize input
+    let clamped = if time_between.is_finite() && time_between >= 0.0 {
+        time_between
+    } else {
+        0.0
+    };
+    let normal_delay = Duration::from_secs_f64(clamped);
+
+    // Precompute fast delay if skipping is enabled
+    let (skip_key, fast_delay) = if let Some((key, fast_time_between)) = skip {
+        let clamped = if fast_time_between.is_finite() && fast_time_between >= 0.0 {
+            fast_time_between
+        } else {
+            0.0
+        };
+        let delay = Duration::from_secs_f64(clamped);
+        (Some(key), delay)
+    } else {
+        (None, Duration::from_millis(0))
+    };
+
+    // If Some(t), we are in fast mode until `t`
+    let mut fast_until: Option<Instant> = None;
+
+    for ch in reveal_units(&wrap(str, terminal_width())) {
+        print!("{ch}");
+        flush();
+
+        // Decide current delay based on whether fast window is active
+        let now = Instant::now();
+        let fast_active = fast_until.map_or(false, |t| now < t);
+        let delay = if fast_active {
+            fast_delay
+        } else {
+            normal_delay
+        };
+
+        // No skip configured → sleep the chosen delay
+        let skip_key = match skip_key.clone() {
+            Some(skip_key) => skip_key,
+            None => {
+                std::thread::sleep(delay);
+                continue;
+            }
+        };
+
+        // Wait up to `delay`, reacting to Tab to (re)enter/extend fast mode
+        match key_pressed_within(delay) {
+            Ok(Some(k)) if k == skip_key => {
+                // Enter/extend fast mode
+                fast_until = Some(Instant::now() + FAST_GRACE);
+
+                // Drain immediate Tabs (zero wait) to keep extending the window
+                while let Ok(Some(k2)) = key_pressed_within(Duration::from_millis(0)) {
+                    if k2 == skip_key {
+                        fast_until = Some(Instant::now() + FAST_GRACE);
+                    } else {
+                        break;
+                    }
+                }
+            }
+            _ => {
+                // Timer expired; if we were in fast mode and grace elapsed, leave it
+                if let Some(t) = fast_until {
+                    if Instant::now() >= t {
+                        fast_until = None;
+                    }
+                }
+            }
+        }
+    }
+}
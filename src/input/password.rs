@@ -0,0 +1,316 @@
+//! Password Input
+//!
+//! Reads secrets like passwords and tokens without echoing them, optionally asking for them twice.
+
+use std::{
+    fmt,
+    io::{self, BufRead},
+    sync::atomic,
+};
+
+use super::{editor::Screen, expect_read, question};
+use crate::{
+    control::{clear_below, flush, is_input_terminal, is_terminal, move_cursor_up},
+    read::{read_key, Key, RawMode},
+    theme::{theme, Theme},
+};
+
+/// A secret read by a [`Password`] prompt.
+///
+/// Its text is only available through [`expose`](Secret::expose), and isn't printed by `Debug`.
+/// The memory holding it is overwritten with zeros when it's dropped, by the `zeroize` crate with
+/// the `zeroize` feature. Input that doesn't come from a terminal is read directly into the
+/// secret, but the buffer of the standard input may keep a copy until later reads overwrite it.
+#[derive(Default)]
+pub struct Secret(String);
+
+impl Secret {
+    /// Returns the text of the secret.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns whether `other` has the same text, taking the same time wherever they differ, so
+    /// the comparison doesn't reveal how much of them matches.
+    fn matches(&self, other: &Secret) -> bool {
+        let (a, b) = (self.0.as_bytes(), other.0.as_bytes());
+        a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+    }
+
+    /// Applies an edit `key`, returns whether it was Enter, finishing the secret.
+    fn edit(&mut self, key: Key) -> bool {
+        match key {
+            Key::Enter => return true,
+            Key::Char(c) if !c.is_control() => self.push(c),
+            Key::Backspace => self.pop(),
+            Key::Ctrl('u') => self.truncate(0),
+            _ => {}
+        }
+        false
+    }
+
+    /// Appends `c`, moving the text to a larger buffer instead of letting the `String` reallocate,
+    /// so no copy is left behind.
+    fn push(&mut self, c: char) {
+        if self.0.len() + c.len_utf8() > self.0.capacity() {
+            let mut grown = String::with_capacity((self.0.capacity() * 2).max(64));
+            grown.push_str(&self.0);
+            self.truncate(0);
+            self.0 = grown;
+        }
+        self.0.push(c);
+    }
+
+    /// Removes the last character.
+    fn pop(&mut self) {
+        let end = self.0.char_indices().next_back().map_or(0, |(i, _)| i);
+        self.truncate(end);
+    }
+
+    /// Appends a line of `input` without its line break.
+    fn read_line(&mut self, input: &mut impl BufRead) -> io::Result<()> {
+        // the bytes of a character split between reads of the buffer
+        let mut partial = [0; 4];
+        let mut len = 0;
+        let result = loop {
+            let buffer = match input.fill_buf() {
+                Ok(buffer) => buffer,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => break Err(e),
+            };
+            let byte = match buffer.first() {
+                Some(&byte) => byte,
+                None => break Ok(()),
+            };
+            input.consume(1);
+            if byte == b'\n' {
+                break Ok(());
+            }
+            partial[len] = byte;
+            len += 1;
+            match std::str::from_utf8(&partial[..len]) {
+                Ok(c) => {
+                    c.chars().for_each(|c| self.push(c));
+                    len = 0;
+                }
+                Err(e) if e.error_len().is_none() => {}
+                Err(_) => break Err(invalid_utf8()),
+            }
+        };
+        let incomplete = len > 0;
+        wipe(&mut partial);
+        if self.0.ends_with('\r') {
+            self.pop();
+        }
+        match result {
+            Ok(()) if incomplete => Err(invalid_utf8()),
+            result => result,
+        }
+    }
+
+    /// Shortens the text to `len` bytes, overwriting the removed part first.
+    fn truncate(&mut self, len: usize) {
+        // SAFETY: zeros are valid UTF-8, and `len` is a character boundary
+        wipe(&mut unsafe { self.0.as_mut_vec() }[len..]);
+        self.0.truncate(len);
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret(***)")
+    }
+}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        #[cfg(feature = "zeroize")]
+        zeroize::Zeroize::zeroize(&mut self.0);
+        // the rest of the buffer was overwritten when the text was shortened or moved
+        #[cfg(not(feature = "zeroize"))]
+        self.truncate(0);
+    }
+}
+
+/// Overwrites `bytes` with zeros, in a way the compiler can't leave out as unused.
+fn wipe(bytes: &mut [u8]) {
+    for byte in bytes.iter_mut() {
+        // SAFETY: the pointer comes from a reference, so it's valid and aligned
+        unsafe { std::ptr::write_volatile(byte, 0) };
+    }
+    atomic::compiler_fence(atomic::Ordering::SeqCst);
+}
+
+/// The error of input that isn't valid UTF-8, like [`BufRead::read_line`] returns.
+fn invalid_utf8() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "stream did not contain valid UTF-8",
+    )
+}
+
+/// A prompt reading a secret without echoing it.
+///
+/// # Example
+///
+/// ```no_run
+/// use console_utils::input::Password;
+///
+/// let password = Password::new("New password")
+///     .mask(Some('•'))
+///     .confirm("Repeat password")
+///     .read();
+/// println!("{} characters", password.expose().chars().count());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Password<'a> {
+    before: &'a str,
+    mask: Option<char>,
+    confirm: Option<&'a str>,
+    theme: Option<&'a Theme>,
+}
+
+impl<'a> Password<'a> {
+    /// Creates a prompt displaying `before`, showing a `*` for each typed character.
+    pub fn new(before: &'a str) -> Self {
+        Self {
+            before,
+            mask: Some('*'),
+            confirm: None,
+            theme: None,
+        }
+    }
+
+    /// Sets the character shown for each typed one, or `None` to show nothing at all.
+    pub fn mask(mut self, mask: Option<char>) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    /// Asks for the secret a second time with the question `before`, starting over with an
+    /// error if the two don't match.
+    pub fn confirm(mut self, before: &'a str) -> Self {
+        self.confirm = Some(before);
+        self
+    }
+
+    /// Sets the theme the prompt is drawn with, instead of the current [`theme`].
+    pub fn theme(mut self, theme: &'a Theme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// Prompts for the secret, and returns it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the console can't be read, see [`try_read`](Password::try_read).
+    pub fn read(self) -> Secret {
+        expect_read(self.try_read())
+    }
+
+    /// Prompts for the secret, and returns it, or the error reading the console, e.g. because
+    /// the input was closed.
+    pub fn try_read(self) -> io::Result<Secret> {
+        let theme = self.theme.cloned().unwrap_or_else(theme);
+        let prompt = format!("{} ", question(&theme, self.before, None));
+        let confirm = self
+            .confirm
+            .map(|before| format!("{} ", question(&theme, before, None)));
+        let terminal = is_terminal();
+        let mut error = String::new();
+        loop {
+            let (secret, mut rows) = self.read_once(&prompt, &error, terminal)?;
+            let confirm = match &confirm {
+                Some(confirm) => confirm,
+                None => return Ok(secret),
+            };
+            let (repeated, confirm_rows) = self.read_once(confirm, "", terminal)?;
+            if secret.matches(&repeated) {
+                return Ok(secret);
+            }
+
+            error = format!(
+                "{} {}",
+                theme.error_prefix,
+                theme.error_style.paint("The entries don't match")
+            );
+            if terminal {
+                rows += confirm_rows;
+                move_cursor_up(rows);
+                clear_below();
+            } else {
+                println!("\n{error}\n");
+            }
+        }
+    }
+
+    /// Reads the secret once after printing `prompt` and the `error` below it, returns it with
+    /// the number of printed rows.
+    fn read_once(&self, prompt: &str, error: &str, terminal: bool) -> io::Result<(Secret, usize)> {
+        if !terminal {
+            print!("{prompt}");
+            flush();
+            // the output may be redirected while the keys still come from a terminal, which
+            // would echo them if they were read as a line
+            if is_input_terminal() {
+                let _raw = RawMode::enable()?;
+                let mut secret = Secret::default();
+                while !secret.edit(read_key()?) {}
+                println!();
+                return Ok((secret, 0));
+            }
+            let mut secret = Secret::default();
+            secret.read_line(&mut io::stdin().lock())?;
+            return Ok((secret, 0));
+        }
+
+        let (head, prompt) = match prompt.rsplit_once('\n') {
+            Some((head, prompt)) => (Some(head), prompt),
+            None => (None, prompt),
+        };
+        if let Some(head) = head {
+            println!("{head}");
+        }
+
+        let _raw = RawMode::enable()?;
+        let mut secret = Secret::default();
+        let mut screen = Screen::default();
+        loop {
+            let shown = self.shown(&secret);
+            screen.draw(prompt, &shown, shown.len(), error);
+            if secret.edit(read_key()?) {
+                break;
+            }
+        }
+        let rows = screen.finish(prompt, &self.shown(&secret));
+        Ok((secret, head.map_or(0, |head| head.lines().count()) + rows))
+    }
+
+    /// Returns the masks shown for the typed characters.
+    fn shown(&self, secret: &Secret) -> String {
+        self.mask.map_or(String::new(), |mask| {
+            mask.to_string().repeat(secret.0.chars().count())
+        })
+    }
+}
+
+/// Reads a secret like a password from the console, showing a `*` for each typed character.
+///
+/// A shortcut for [`Password::new(before).read()`](Password), which can also hide the input
+/// completely or ask for a confirmation.
+///
+/// # Arguments
+///
+/// * `before` - The text to display before prompting for the secret.
+///
+/// # Returns
+///
+/// Returns the entered [`Secret`].
+///
+/// # Panics
+///
+/// Panics if the console can't be read, e.g. because the input was closed.
+pub fn password(before: &str) -> Secret {
+    Password::new(before).read()
+}
@@ -2,6 +2,10 @@
 #![doc = include_str!("../README.md")]
 
 pub mod control;
+pub mod diagnostic;
 pub mod input;
+pub mod panel;
 pub mod read;
 pub mod styled;
+pub mod table;
+pub mod theme;
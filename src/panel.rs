@@ -0,0 +1,366 @@
+//! Panel Utilities
+//!
+//! This module provides bordered boxes around styled text with [`Panel`], e.g. for summaries at
+//! the end of a wizard, and full-width separators with [`Rule`]. Both share the [`Border`] and
+//! [`Align`] types of [tables](crate::table).
+
+use std::fmt;
+
+use crate::{
+    styled::{parse_ansi, truncate_to_width, wrap, Style, StyledString},
+    table::{max_width, Align, Border},
+};
+
+/// A box drawn around styled text, with an optional title in its top border.
+///
+/// The panel is as wide as its content, up to the [`width`](Panel::width) or the width of the
+/// terminal, or 80 columns if it's unknown. Longer lines are wrapped.
+///
+/// # Example
+///
+/// ```rust
+/// use console_utils::{
+///     panel::Panel,
+///     styled::{Color, Style, StyledText},
+///     table::Align,
+/// };
+///
+/// let panel = Panel::new("Installed 3 packages\nin 1.2s")
+///     .title(StyledText::new("Done").fg(Color::Green).bold())
+///     .border_style(Style::new().fg(Color::Green))
+///     .align(Align::Center)
+///     .padding(0, 2);
+/// println!("{panel}");
+/// assert_eq!(
+///     panel.render().plain_text(),
+///     "╭─ Done ─────────────────╮\n\
+///      │  Installed 3 packages  │\n\
+///      │        in 1.2s         │\n\
+///      ╰────────────────────────╯"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Panel {
+    title: Option<StyledString>,
+    body: StyledString,
+    border: Border,
+    border_style: Style,
+    padding: (usize, usize),
+    align: Align,
+    title_align: Align,
+    width: Option<usize>,
+    expand: bool,
+}
+
+impl Panel {
+    /// Creates a panel with rounded borders around `body`.
+    pub fn new(body: impl Into<StyledString>) -> Self {
+        Self {
+            title: None,
+            body: body.into(),
+            border: Border::default(),
+            border_style: Style::new(),
+            padding: (0, 1),
+            align: Align::Left,
+            title_align: Align::Left,
+            width: None,
+            expand: false,
+        }
+    }
+
+    /// Sets the title shown in the top border.
+    pub fn title(mut self, title: impl Into<StyledString>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the border style.
+    pub fn border(mut self, border: Border) -> Self {
+        self.border = border;
+        self
+    }
+
+    /// Sets the style of the border, e.g. its color.
+    pub fn border_style(mut self, style: Style) -> Self {
+        self.border_style = style;
+        self
+    }
+
+    /// Sets the number of empty lines above and below the body and the number of spaces left
+    /// and right of it. Defaults to `(0, 1)`.
+    pub fn padding(mut self, vertical: usize, horizontal: usize) -> Self {
+        self.padding = (vertical, horizontal);
+        self
+    }
+
+    /// Sets the alignment of the lines of the body.
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Sets the alignment of the title inside the top border.
+    pub fn title_align(mut self, align: Align) -> Self {
+        self.title_align = align;
+        self
+    }
+
+    /// Sets the maximum width of the panel including its border, instead of the width of the
+    /// terminal.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Makes the panel as wide as possible instead of fitting it to its content.
+    pub fn expand(mut self) -> Self {
+        self.expand = true;
+        self
+    }
+
+    /// Renders the panel into a styled string.
+    pub fn render(&self) -> StyledString {
+        let chars = self.border.chars();
+        let (vertical, horizontal) = self.padding;
+        let edges = if chars.is_some() { 2 } else { 0 };
+        let max_inner = max_width(self.width)
+            .saturating_sub(edges + 2 * horizontal)
+            .max(1);
+
+        let lines: Vec<StyledString> = self
+            .body
+            .lines()
+            .iter()
+            .flat_map(|line| parse_ansi(&wrap(&line.format_sequence(), max_inner)).lines())
+            .collect();
+        let title = self.title.as_ref().map(|title| {
+            // room for the title, a space on both sides and at least one line character
+            let room = (max_inner + 2 * horizontal).saturating_sub(4).max(1);
+            parse_ansi(&truncate_to_width(&title.format_sequence(), room, "…"))
+        });
+        let inner = if self.expand {
+            max_inner
+        } else {
+            let body = lines.iter().map(StyledString::width).max().unwrap_or(0);
+            let title = title
+                .as_ref()
+                .map_or(0, |t| (t.width() + 4).saturating_sub(2 * horizontal));
+            body.max(title).min(max_inner)
+        };
+        let span = inner + 2 * horizontal;
+
+        let mut out = StyledString::new();
+        match (&chars, &title) {
+            (Some(chars), title) => {
+                let [left, line, _, right] = chars.top;
+                out.push_str(left, self.border_style);
+                push_line(
+                    &mut out,
+                    title.as_ref(),
+                    line,
+                    span,
+                    self.title_align,
+                    self.border_style,
+                );
+                out.push_str(right, self.border_style);
+            }
+            (None, Some(title)) => {
+                push_line(
+                    &mut out,
+                    Some(title),
+                    " ",
+                    span,
+                    self.title_align,
+                    Style::new(),
+                );
+            }
+            (None, None) => {}
+        }
+
+        let empty = StyledString::new();
+        let padding = (0..vertical).map(|_| &empty);
+        let body = padding.clone().chain(&lines).chain(padding);
+        for line in body {
+            if !out.is_empty() {
+                out.push_str("\n", Style::new());
+            }
+            if let Some(chars) = &chars {
+                out.push_str(chars.vertical, self.border_style);
+            }
+            let (before, after) = self.align.split(inner.saturating_sub(line.width()));
+            out.push_str(&" ".repeat(horizontal + before), Style::new());
+            out.extend(line.spans().iter().cloned());
+            out.push_str(&" ".repeat(after + horizontal), Style::new());
+            if let Some(chars) = &chars {
+                out.push_str(chars.vertical, self.border_style);
+            }
+        }
+
+        if let Some(chars) = &chars {
+            let [left, line, _, right] = chars.bottom;
+            out.push_str("\n", Style::new());
+            out.push_str(left, self.border_style);
+            out.push_str(&line.repeat(span), self.border_style);
+            out.push_str(right, self.border_style);
+        }
+        out
+    }
+}
+
+impl fmt::Display for Panel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+/// Creates a [`Panel`] with a title around `body`.
+///
+/// # Example
+///
+/// ```rust
+/// use console_utils::panel::panel;
+///
+/// println!("{}", panel("Summary", "Name: demo\nLicense: MIT"));
+/// ```
+pub fn panel(title: impl Into<StyledString>, body: impl Into<StyledString>) -> Panel {
+    Panel::new(body).title(title)
+}
+
+/// A horizontal line across the terminal, with an optional title.
+///
+/// # Example
+///
+/// ```rust
+/// use console_utils::{panel::Rule, table::Align};
+///
+/// let rule = Rule::new().title("Section").align(Align::Left).width(20);
+/// assert_eq!(rule.render().plain_text(), "─ Section ──────────");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    title: Option<StyledString>,
+    border: Border,
+    style: Style,
+    align: Align,
+    width: Option<usize>,
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Self {
+            title: None,
+            border: Border::default(),
+            style: Style::new(),
+            align: Align::Center,
+            width: None,
+        }
+    }
+}
+
+impl Rule {
+    /// Creates a thin line without a title.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the title shown in the line.
+    pub fn title(mut self, title: impl Into<StyledString>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the characters the line is drawn with, see [`Border`].
+    pub fn border(mut self, border: Border) -> Self {
+        self.border = border;
+        self
+    }
+
+    /// Sets the style of the line, e.g. its color.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the alignment of the title, centered by default.
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Sets the width of the line, instead of the width of the terminal or 80 columns if it's
+    /// unknown.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Renders the line into a styled string.
+    pub fn render(&self) -> StyledString {
+        let width = max_width(self.width);
+        let line = self.border.chars().map_or(" ", |chars| chars.top[1]);
+        let title = self.title.as_ref().map(|title| {
+            let room = width.saturating_sub(4).max(1);
+            parse_ansi(&truncate_to_width(&title.format_sequence(), room, "…"))
+        });
+        let mut out = StyledString::new();
+        push_line(
+            &mut out,
+            title.as_ref(),
+            line,
+            width,
+            self.align,
+            self.style,
+        );
+        out
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+/// Creates a full-width [`Rule`] with a centered title.
+///
+/// # Example
+///
+/// ```rust
+/// use console_utils::panel::rule;
+///
+/// println!("{}", rule("Section"));
+/// ```
+pub fn rule(title: impl Into<StyledString>) -> Rule {
+    Rule::new().title(title)
+}
+
+/// Appends a `width` cells long line drawn with `line`, with `title` embedded.
+///
+/// A left or right aligned title keeps one line character towards its edge.
+fn push_line(
+    out: &mut StyledString,
+    title: Option<&StyledString>,
+    line: &str,
+    width: usize,
+    title_align: Align,
+    style: Style,
+) {
+    let title = match title.filter(|t| !t.is_empty()) {
+        Some(title) => title,
+        None => {
+            out.push_str(&line.repeat(width), style);
+            return;
+        }
+    };
+    let rest = width.saturating_sub(title.width() + 2);
+    let (before, after) = match title_align {
+        Align::Left => (rest.min(1), rest.saturating_sub(1)),
+        Align::Center => Align::Center.split(rest),
+        Align::Right => (rest.saturating_sub(1), rest.min(1)),
+    };
+    out.push_str(&line.repeat(before), style);
+    out.push_str(" ", Style::new());
+    out.extend(title.spans().iter().cloned());
+    out.push_str(" ", Style::new());
+    out.push_str(&line.repeat(after), style);
+}
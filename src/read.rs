@@ -6,7 +6,10 @@
 use std::{io, time::Duration};
 
 /// Represents different keyboard keys that can be captured by the `read_key` function.
+///
+/// More keys may be added in minor releases, so matches need a wildcard arm.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Key {
     /// Arrow up key.
     ArrowUp,
@@ -24,13 +27,34 @@ pub enum Key {
     Backspace,
     /// Escape key.
     Escape,
+    /// Home key.
+    Home,
+    /// End key.
+    End,
+    /// Insert key.
+    Insert,
+    /// Delete key.
+    Delete,
+    /// Page up key.
+    PageUp,
+    /// Page down key.
+    PageDown,
     /// Any printable character on the keyboard.
     Char(char),
+    /// A letter pressed together with Ctrl, in lowercase, e.g. `Ctrl('a')`.
+    ///
+    /// Unix terminals can't tell Ctrl-H, Ctrl-I and Ctrl-J or Ctrl-M apart from Backspace, Tab
+    /// and Enter, which they are reported as. Ctrl-C usually interrupts the program instead.
+    Ctrl(char),
+    /// A character typed together with Alt, e.g. `Alt('b')`.
+    Alt(char),
     /// Any unrecognized key.
     Unknown,
 }
 
 /// Reads a single key event from the console input and returns a `Key` enum.
+///
+/// Fails with [`io::ErrorKind::UnexpectedEof`] once the input is closed.
 pub fn read_key() -> io::Result<Key> {
     #[cfg(windows)]
     {
@@ -43,6 +67,23 @@ pub fn read_key() -> io::Result<Key> {
     }
 }
 
+/// Keeps the terminal from echoing input and buffering it into lines while alive, so keys can be
+/// read one after another without being printed in between.
+pub(crate) struct RawMode {
+    #[cfg(unix)]
+    _guard: unix::RawMode,
+}
+
+impl RawMode {
+    /// Enables raw mode until the returned guard is dropped.
+    pub(crate) fn enable() -> io::Result<Self> {
+        Ok(Self {
+            #[cfg(unix)]
+            _guard: unix::RawMode::new(libc::STDIN_FILENO)?,
+        })
+    }
+}
+
 /// Waits for a key press for up to the specified `timeout` duration.
 pub fn key_pressed_within(timeout: Duration) -> io::Result<Option<Key>> {
     #[cfg(windows)]
@@ -67,7 +108,8 @@ pub mod windows {
     use windows_sys::Win32::Foundation::{INVALID_HANDLE_VALUE, WAIT_OBJECT_0, WAIT_TIMEOUT};
     use windows_sys::Win32::System::Console::{
         GetStdHandle, PeekConsoleInputW, ReadConsoleInputW, INPUT_RECORD, KEY_EVENT,
-        KEY_EVENT_RECORD, STD_INPUT_HANDLE,
+        KEY_EVENT_RECORD, LEFT_ALT_PRESSED, LEFT_CTRL_PRESSED, RIGHT_ALT_PRESSED,
+        RIGHT_CTRL_PRESSED, STD_INPUT_HANDLE,
     };
     use windows_sys::Win32::System::Threading::WaitForSingleObject;
     use windows_sys::Win32::UI::Input::KeyboardAndMouse;
@@ -94,6 +136,12 @@ pub mod windows {
                 let key_event: KEY_EVENT_RECORD = unsafe { mem::transmute(buffer.Event) };
 
                 if key_event.bKeyDown != 0 {
+                    let state = key_event.dwControlKeyState;
+                    let ctrl = state & (LEFT_CTRL_PRESSED | RIGHT_CTRL_PRESSED) != 0;
+                    let alt = state & (LEFT_ALT_PRESSED | RIGHT_ALT_PRESSED) != 0;
+                    // SAFETY: the Unicode variant is filled by ReadConsoleInputW.
+                    let unicode = unsafe { key_event.uChar.UnicodeChar };
+
                     return match key_event.wVirtualKeyCode {
                         KeyboardAndMouse::VK_UP => Ok(Key::ArrowUp),
                         KeyboardAndMouse::VK_DOWN => Ok(Key::ArrowDown),
@@ -103,7 +151,21 @@ pub mod windows {
                         KeyboardAndMouse::VK_TAB => Ok(Key::Tab),
                         KeyboardAndMouse::VK_BACK => Ok(Key::Backspace),
                         KeyboardAndMouse::VK_ESCAPE => Ok(Key::Escape),
-                        c => Ok(Key::Char(char::from_u32(c as u32).unwrap_or_default())),
+                        KeyboardAndMouse::VK_HOME => Ok(Key::Home),
+                        KeyboardAndMouse::VK_END => Ok(Key::End),
+                        KeyboardAndMouse::VK_INSERT => Ok(Key::Insert),
+                        KeyboardAndMouse::VK_DELETE => Ok(Key::Delete),
+                        KeyboardAndMouse::VK_PRIOR => Ok(Key::PageUp),
+                        KeyboardAndMouse::VK_NEXT => Ok(Key::PageDown),
+                        // letters with Ctrl, but not AltGr which is reported as Ctrl and Alt
+                        c @ 0x41..=0x5A if ctrl && !alt => {
+                            Ok(Key::Ctrl((c as u8 as char).to_ascii_lowercase()))
+                        }
+                        _ => Ok(match char::from_u32(unicode as u32) {
+                            Some(c) if !c.is_control() && alt && !ctrl => Key::Alt(c),
+                            Some(c) if !c.is_control() => Key::Char(c),
+                            _ => Key::Unknown,
+                        }),
                     };
                 }
             }
@@ -184,9 +246,10 @@ pub mod windows {
 #[cfg(unix)]
 pub mod unix {
     use libc::{
-        poll, pollfd, tcgetattr, tcsetattr, termios, ECHO, ICANON, POLLIN, STDIN_FILENO, TCSANOW,
+        c_void, poll, pollfd, read, tcgetattr, tcsetattr, termios, ECHO, ICANON, POLLIN,
+        STDIN_FILENO, TCSANOW,
     };
-    use std::io::{self, Read};
+    use std::io;
     use std::mem;
     use std::time::Duration;
 
@@ -215,13 +278,13 @@ pub mod unix {
     }
 
     /// RAII guard that disables canonical mode and echo, restoring on drop.
-    struct RawMode {
+    pub(crate) struct RawMode {
         fd: i32,
         saved: termios,
     }
 
     impl RawMode {
-        fn new(fd: i32) -> io::Result<Self> {
+        pub(crate) fn new(fd: i32) -> io::Result<Self> {
             let mut current = get_termios(fd)?;
             let saved = current;
 
@@ -243,43 +306,151 @@ pub mod unix {
         }
     }
 
-    /// Read a single key assuming we are already in raw/no-echo mode.
-    fn read_key_raw() -> io::Result<Key> {
-        let mut buffer = [0u8; 3];
+    /// How long to wait for the rest of an escape sequence or character, which terminals send
+    /// at once, before treating a lone escape byte as the Escape key.
+    const SEQUENCE_TIMEOUT: Duration = Duration::from_millis(25);
 
-        // We read up to 3 bytes so we can catch arrow escape sequences.
-        let n = std::io::stdin().read(&mut buffer)?;
+    /// Waits up to `timeout` for input, forever if `None`. Returns whether there is any.
+    fn wait(timeout: Option<Duration>) -> io::Result<bool> {
+        let mut fds = pollfd {
+            fd: STDIN_FILENO,
+            events: POLLIN,
+            revents: 0,
+        };
 
-        if n == 0 {
-            // EOF or nothing read — treat as unknown
-            return Ok(Key::Unknown);
+        // Clamp to i32::MAX safely, negative waits forever.
+        let ms = timeout.map_or(-1, |t| t.as_millis().min(i32::MAX as u128) as i32);
+
+        let rc = unsafe { poll(&mut fds as *mut pollfd, 1, ms) };
+        if rc < 0 {
+            return Err(io::Error::last_os_error());
         }
+        Ok(rc > 0)
+    }
 
-        match buffer[0] {
-            27 => {
-                // Escape sequence: try to match ESC [ A/B/C/D
-                // Only proceed if we actually got those extra bytes.
-                if n >= 3 && buffer[1] == b'[' {
-                    match buffer[2] {
-                        b'A' => Ok(Key::ArrowUp),
-                        b'B' => Ok(Key::ArrowDown),
-                        b'C' => Ok(Key::ArrowRight),
-                        b'D' => Ok(Key::ArrowLeft),
-                        _ => Ok(Key::Unknown),
-                    }
-                } else if n == 1 {
-                    Ok(Key::Escape)
-                } else {
-                    Ok(Key::Unknown)
-                }
+    /// Reads a single byte, `None` on EOF or if nothing arrives within `timeout`.
+    ///
+    /// Reads directly from the file descriptor, as the buffer of `io::stdin` would hide pending
+    /// input from `poll`.
+    fn read_byte(timeout: Option<Duration>) -> io::Result<Option<u8>> {
+        if timeout.is_some() && !wait(timeout)? {
+            return Ok(None);
+        }
+        let mut byte = 0u8;
+        let n = unsafe { read(STDIN_FILENO, &mut byte as *mut u8 as *mut c_void, 1) };
+        match n {
+            n if n < 0 => Err(io::Error::last_os_error()),
+            0 => Ok(None),
+            _ => Ok(Some(byte)),
+        }
+    }
+
+    /// Read a single key assuming we are already in raw/no-echo mode.
+    fn read_key_raw() -> io::Result<Key> {
+        let byte = match read_byte(None)? {
+            Some(byte) => byte,
+            // the input was closed, no key will ever arrive
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "the input was closed",
+                ))
             }
-            b'\n' => Ok(Key::Enter),
+        };
+
+        match byte {
+            27 => read_escape(),
+            b'\n' | b'\r' => Ok(Key::Enter),
             b'\t' => Ok(Key::Tab),
-            127 => Ok(Key::Backspace),
+            8 | 127 => Ok(Key::Backspace),
+            1..=26 => Ok(Key::Ctrl((b'a' + byte - 1) as char)),
+            0x80.. => read_utf8(byte),
             c => Ok(Key::Char(c as char)),
         }
     }
 
+    /// Reads the rest of an escape sequence, after the escape byte.
+    fn read_escape() -> io::Result<Key> {
+        let byte = match read_byte(Some(SEQUENCE_TIMEOUT))? {
+            Some(byte) => byte,
+            None => return Ok(Key::Escape),
+        };
+
+        match byte {
+            b'[' => read_csi(),
+            // application mode, e.g. ESC O A
+            b'O' => Ok(match read_byte(Some(SEQUENCE_TIMEOUT))? {
+                Some(b'A') => Key::ArrowUp,
+                Some(b'B') => Key::ArrowDown,
+                Some(b'C') => Key::ArrowRight,
+                Some(b'D') => Key::ArrowLeft,
+                Some(b'H') => Key::Home,
+                Some(b'F') => Key::End,
+                _ => Key::Unknown,
+            }),
+            27 => Ok(Key::Escape),
+            0x20..=0x7e => Ok(Key::Alt(byte as char)),
+            0x80.. => Ok(match read_utf8(byte)? {
+                Key::Char(c) => Key::Alt(c),
+                key => key,
+            }),
+            _ => Ok(Key::Unknown),
+        }
+    }
+
+    /// Reads a control sequence after `ESC [`, like `ESC [ 3 ~` for Delete.
+    fn read_csi() -> io::Result<Key> {
+        let mut params = Vec::new();
+        let last = loop {
+            match read_byte(Some(SEQUENCE_TIMEOUT))? {
+                Some(byte @ 0x40..=0x7e) => break byte,
+                Some(byte) if params.len() < 16 => params.push(byte),
+                _ => return Ok(Key::Unknown),
+            }
+        };
+        // modifiers after a `;` are ignored, e.g. Ctrl+Left is reported as Left
+        let first = params
+            .split(|b| *b == b';')
+            .next()
+            .and_then(|p| std::str::from_utf8(p).ok())
+            .and_then(|p| p.parse::<u32>().ok());
+
+        Ok(match (last, first) {
+            (b'A', _) => Key::ArrowUp,
+            (b'B', _) => Key::ArrowDown,
+            (b'C', _) => Key::ArrowRight,
+            (b'D', _) => Key::ArrowLeft,
+            (b'H', _) | (b'~', Some(1 | 7)) => Key::Home,
+            (b'F', _) | (b'~', Some(4 | 8)) => Key::End,
+            (b'~', Some(2)) => Key::Insert,
+            (b'~', Some(3)) => Key::Delete,
+            (b'~', Some(5)) => Key::PageUp,
+            (b'~', Some(6)) => Key::PageDown,
+            _ => Key::Unknown,
+        })
+    }
+
+    /// Reads the rest of a UTF-8 encoded character starting with `first`.
+    fn read_utf8(first: u8) -> io::Result<Key> {
+        let len = match first {
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf7 => 4,
+            _ => return Ok(Key::Unknown),
+        };
+        let mut bytes = [first, 0, 0, 0];
+        for byte in &mut bytes[1..len] {
+            match read_byte(Some(SEQUENCE_TIMEOUT))? {
+                Some(b) => *byte = b,
+                None => return Ok(Key::Unknown),
+            }
+        }
+        Ok(std::str::from_utf8(&bytes[..len])
+            .ok()
+            .and_then(|s| s.chars().next())
+            .map_or(Key::Unknown, Key::Char))
+    }
+
     // Reads a key from the console, temporarily switching to raw/no-echo.
     pub(crate) fn read_key() -> io::Result<Key> {
         let _rm = RawMode::new(STDIN_FILENO)?;
@@ -291,21 +462,7 @@ pub mod unix {
     pub(super) fn key_pressed_within(timeout: Duration) -> io::Result<Option<Key>> {
         let _rm = RawMode::new(STDIN_FILENO)?;
 
-        let mut fds = pollfd {
-            fd: STDIN_FILENO,
-            events: POLLIN,
-            revents: 0,
-        };
-
-        // Clamp to i32::MAX safely.
-        let ms = timeout.as_millis().min(i32::MAX as u128) as i32;
-
-        let rc = unsafe { poll(&mut fds as *mut pollfd, 1, ms) };
-
-        if rc < 0 {
-            return Err(io::Error::last_os_error());
-        }
-        if rc == 0 {
+        if !wait(Some(timeout))? {
             // timeout — nothing was pressed
             return Ok(None);
         }
@@ -0,0 +1,354 @@
+//! ANSI-aware String Utilities
+//!
+//! Helpers for measuring and cutting strings that contain ANSI escape sequences, like the
+//! output of [`StyledText`](super::StyledText). Widths are counted in terminal cells: East Asian
+//! wide characters and emoji take two cells, combining marks and escape sequences none.
+//!
+//! Formatted output can also be turned back into a [`StyledString`] with [`parse_ansi`].
+
+use std::borrow::Cow;
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthChar;
+
+use super::{Color, Link, Style, StyledString, StyledText, Underline, NAMED_COLORS};
+
+/// A piece of a string, either visible text or an escape sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Segment<'a> {
+    /// Text without any escape sequences.
+    Text(&'a str),
+    /// A complete escape sequence including the leading `ESC`.
+    Escape(&'a str),
+}
+
+/// Splits `s` into text and escape sequences.
+///
+/// Recognizes CSI sequences (`ESC [ … final`), OSC sequences (`ESC ] … BEL` or `ESC ] … ESC \`)
+/// and two byte escapes. An unterminated sequence at the end is treated as an escape.
+pub(crate) fn segments(s: &str) -> Segments<'_> {
+    Segments { rest: s }
+}
+
+/// Iterator returned by [`segments`].
+pub(crate) struct Segments<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Segments<'a> {
+    type Item = Segment<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let b = self.rest.as_bytes();
+        if b[0] != 0x1b {
+            let end = self.rest.find('\x1b').unwrap_or(self.rest.len());
+            let (text, rest) = self.rest.split_at(end);
+            self.rest = rest;
+            return Some(Segment::Text(text));
+        }
+        let len = match b.get(1) {
+            // CSI: parameter and intermediate bytes, terminated by a final byte
+            Some(b'[') => b[2..]
+                .iter()
+                .position(|c| (0x40..=0x7e).contains(c))
+                .map_or(b.len(), |i| i + 3),
+            // OSC: terminated by BEL or ST (`ESC \`)
+            Some(b']') => (2..b.len())
+                .find_map(|i| match b[i] {
+                    0x07 => Some(i + 1),
+                    0x1b if b.get(i + 1) == Some(&b'\\') => Some(i + 2),
+                    _ => None,
+                })
+                .unwrap_or(b.len()),
+            Some(_) => 1 + self.rest[1..].chars().next().map_or(0, char::len_utf8),
+            None => 1,
+        };
+        let (escape, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        Some(Segment::Escape(escape))
+    }
+}
+
+/// Returns the number of terminal cells a single grapheme occupies.
+///
+/// Control characters are not printed and therefore take no space. Otherwise a grapheme takes
+/// the cells of its base character, as combining marks and the parts of emoji sequences joined to
+/// it are drawn on top of it. Emoji presentation and flags take two cells.
+pub(crate) fn grapheme_width(grapheme: &str) -> usize {
+    let mut chars = grapheme.chars();
+    match chars.next() {
+        None => 0,
+        Some(_) if grapheme.chars().all(char::is_control) => 0,
+        Some(_) if grapheme.contains('\u{fe0f}') => 2,
+        Some('\u{1f1e6}'..='\u{1f1ff}') if chars.next().is_some() => 2,
+        Some(base) => base.width().unwrap_or(0),
+    }
+}
+
+/// Removes all ANSI escape sequences from `s`.
+///
+/// # Example
+///
+/// ```rust
+/// use console_utils::styled::{strip_ansi, Color, StyledText};
+///
+/// let text = StyledText::new("Hello").fg(Color::Red).to_string();
+/// assert_eq!(strip_ansi(&text), "Hello");
+/// ```
+pub fn strip_ansi(s: &str) -> Cow<'_, str> {
+    if !s.contains('\x1b') {
+        return Cow::Borrowed(s);
+    }
+    Cow::Owned(
+        segments(s)
+            .filter_map(|segment| match segment {
+                Segment::Text(text) => Some(text),
+                Segment::Escape(_) => None,
+            })
+            .collect(),
+    )
+}
+
+/// Returns the number of terminal cells `s` occupies when printed.
+///
+/// Escape sequences and control characters take no space, East Asian wide characters and emoji
+/// take two cells, combining marks are counted together with their base character. For text
+/// spanning multiple lines the width of the widest line is returned.
+///
+/// # Example
+///
+/// ```rust
+/// use console_utils::styled::{display_width, Color, StyledText};
+///
+/// let text = StyledText::new("日本").fg(Color::Red).to_string();
+/// assert_eq!(display_width(&text), 4);
+/// assert_eq!(display_width("e\u{301}"), 1);
+/// ```
+pub fn display_width(s: &str) -> usize {
+    strip_ansi(s)
+        .split('\n')
+        .map(|line| line.graphemes(true).map(grapheme_width).sum())
+        .max()
+        .unwrap_or(0)
+}
+
+/// Shortens `s` to at most `width` terminal cells, ending it with `ellipsis` if it was cut.
+///
+/// Graphemes are never split and escape sequences are kept: the ellipsis is printed with the
+/// style active at the cut, and all escape sequences following the cut are still emitted, so
+/// styles and colors that are reset at the end of `s` are reset in the result as well. `s` is
+/// treated as a single line, anything after a line break is cut.
+///
+/// # Example
+///
+/// ```rust
+/// use console_utils::styled::{display_width, truncate_to_width};
+///
+/// let cut = truncate_to_width("Hello World", 8, "…");
+/// assert_eq!(cut, "Hello W…");
+/// assert_eq!(display_width(&cut), 8);
+/// ```
+pub fn truncate_to_width<'a>(s: &'a str, width: usize, ellipsis: &str) -> Cow<'a, str> {
+    if display_width(s) <= width && !s.contains('\n') {
+        return Cow::Borrowed(s);
+    }
+    let ellipsis = if display_width(ellipsis) > width {
+        ""
+    } else {
+        ellipsis
+    };
+    let available = width - display_width(ellipsis);
+
+    let mut out = String::with_capacity(s.len());
+    let mut used = 0;
+    let mut cut = false;
+    for segment in segments(s) {
+        match segment {
+            Segment::Escape(escape) => out.push_str(escape),
+            Segment::Text(_) if cut => {}
+            Segment::Text(text) => {
+                for grapheme in text.graphemes(true) {
+                    let w = grapheme_width(grapheme);
+                    if grapheme.contains('\n') || used + w > available {
+                        out.push_str(ellipsis);
+                        cut = true;
+                        break;
+                    }
+                    used += w;
+                    out.push_str(grapheme);
+                }
+            }
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Parses text containing SGR escape sequences back into a [`StyledString`].
+///
+/// All colors and attributes this crate emits are understood, including resets, 256-color
+/// (`38;5;n`) and RGB (`38;2;r;g;b`) colors in both their `;` and `:` separated forms. Other
+/// escape sequences, like cursor movements, are dropped. Raw [`Color::ANSI`] codes are parsed
+/// into what they stand for, so `Color::ANSI(31)` comes back as [`Color::Red`]. Underline colors
+/// of the first 16 palette entries come back as named colors, as that's how they are emitted.
+/// An underline color without an underline isn't emitted at all, so it's lost. OSC 8 hyperlinks
+/// become the [`Link`] of their spans.
+///
+/// Apart from that, this is the inverse of [`StyledString::format_sequence`]:
+/// `parse_ansi(&s.format_sequence()) == s`. Printing with `Display` instead adapts the output
+/// to the terminal, which may drop colors and attributes.
+///
+/// # Example
+///
+/// ```rust
+/// use console_utils::styled::{markup, parse_ansi};
+///
+/// let text = markup("[bold red]Error:[/] file missing").unwrap();
+/// assert_eq!(parse_ansi(&text.format_sequence()), text);
+/// ```
+pub fn parse_ansi(s: &str) -> StyledString {
+    let mut out = StyledString::new();
+    let mut style = Style::new();
+    let mut link = None;
+    for segment in segments(s) {
+        match segment {
+            Segment::Text(text) => out.push(StyledText {
+                text: Cow::Borrowed(text),
+                style,
+                link: link.clone(),
+            }),
+            Segment::Escape(escape) => {
+                if let Some(params) = escape
+                    .strip_prefix("\x1b[")
+                    .and_then(|e| e.strip_suffix('m'))
+                {
+                    apply_sgr(&mut style, params);
+                } else if let Some(osc) = escape.strip_prefix("\x1b]8;") {
+                    link = parse_link(osc);
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Parses the parameters and URL of an OSC 8 sequence, `None` if it ends a link.
+pub(super) fn parse_link(osc: &str) -> Option<Link> {
+    let osc = osc
+        .strip_suffix("\x1b\\")
+        .or_else(|| osc.strip_suffix('\x07'))
+        .unwrap_or(osc);
+    let (params, url) = osc.split_once(';')?;
+    if url.is_empty() {
+        return None;
+    }
+    let link = Link::new(url);
+    match params.split(':').find_map(|p| p.strip_prefix("id=")) {
+        Some(id) => Some(link.with_id(id)),
+        None => Some(link),
+    }
+}
+
+/// Applies the `;` separated SGR parameters to `style`.
+fn apply_sgr(style: &mut Style, params: &str) {
+    let params: Vec<&str> = params.split(';').collect();
+    let mut i = 0;
+    while i < params.len() {
+        let mut sub = params[i].split(':');
+        let code = match sub.next().unwrap_or_default() {
+            "" => 0,
+            code => code.parse::<u16>().unwrap_or(u16::MAX),
+        };
+        match code {
+            0 => *style = Style::new(),
+            1 => style.bold = true,
+            2 => style.dim = true,
+            3 => style.italic = true,
+            4 => {
+                style.underline = match sub.next() {
+                    Some("0") => None,
+                    Some("2") => Some(Underline::Double),
+                    Some("3") => Some(Underline::Curly),
+                    Some("4") => Some(Underline::Dotted),
+                    _ => Some(Underline::Single),
+                }
+            }
+            5 | 6 => style.blink = true,
+            7 => style.reverse = true,
+            8 => style.hidden = true,
+            9 => style.strikethrough = true,
+            21 => style.underline = Some(Underline::Double),
+            22 => {
+                style.bold = false;
+                style.dim = false;
+            }
+            23 => style.italic = false,
+            24 => style.underline = None,
+            25 => style.blink = false,
+            27 => style.reverse = false,
+            28 => style.hidden = false,
+            29 => style.strikethrough = false,
+            53 => style.overline = true,
+            55 => style.overline = false,
+            59 => style.underline_color = None,
+            30..=37 => style.fg = Some(basic_color(code - 30, false)),
+            39 => style.fg = None,
+            40..=47 => style.bg = Some(basic_color(code - 40, false)),
+            49 => style.bg = None,
+            90..=97 => style.fg = Some(basic_color(code - 90, true)),
+            100..=107 => style.bg = Some(basic_color(code - 100, true)),
+            38 | 48 | 58 => {
+                let color = if params[i].contains(':') {
+                    extended_color(&sub.collect::<Vec<_>>(), true).0
+                } else {
+                    let (color, used) = extended_color(&params[i + 1..], false);
+                    i += used;
+                    color
+                };
+                match code {
+                    38 => style.fg = color.or(style.fg),
+                    48 => style.bg = color.or(style.bg),
+                    _ => {
+                        // named colors can only be emitted as palette indices
+                        let color = match color {
+                            Some(Color::Indexed(i @ 0..=15)) => {
+                                Some(basic_color(i as u16 % 8, i > 7))
+                            }
+                            color => color,
+                        };
+                        style.underline_color = color.or(style.underline_color);
+                    }
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+}
+
+/// Returns the color with the given offset in the 16-color palette.
+fn basic_color(offset: u16, bright: bool) -> Color {
+    NAMED_COLORS[offset as usize + if bright { 8 } else { 0 }].1
+}
+
+/// Parses the arguments following a `38`/`48` code, returning the color and the number of
+/// arguments it used.
+///
+/// The `:` separated RGB form may contain a color space id, `38:2::r:g:b`.
+fn extended_color(args: &[&str], colon: bool) -> (Option<Color>, usize) {
+    let num = |i: usize| args.get(i).and_then(|a| a.parse::<u8>().ok());
+    match args.first().copied() {
+        Some("5") => (num(1).map(Color::Indexed), 2),
+        Some("2") => {
+            let first = if colon && args.len() > 4 { 2 } else { 1 };
+            let color = match (num(first), num(first + 1), num(first + 2)) {
+                (Some(r), Some(g), Some(b)) => Some(Color::Rgb(r, g, b)),
+                _ => None,
+            };
+            (color, 4)
+        }
+        _ => (None, 0),
+    }
+}
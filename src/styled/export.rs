@@ -0,0 +1,598 @@
+//! HTML and SVG Export
+//!
+//! Renders [`StyledString`]s into HTML fragments or standalone SVG "terminal screenshots", e.g.
+//! for publishing CLI transcripts in documentation. Colors are resolved through a [`Palette`],
+//! which maps every [`Color`] variant to a hex color.
+
+use std::fmt::Write;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::{
+    ansi::grapheme_width, display_width, Color, Style, StyledString, Underline, NAMED_COLORS,
+};
+
+/// An RGB color value.
+type Rgb = (u8, u8, u8);
+
+/// Maps the colors of the terminal to concrete RGB values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    pub(super) colors: [Rgb; 16],
+    pub(super) foreground: Rgb,
+    pub(super) background: Rgb,
+}
+
+impl Default for Palette {
+    /// A dark palette similar to the ones of common terminal emulators.
+    fn default() -> Self {
+        Self {
+            colors: [
+                (0x1e, 0x1e, 0x1e),
+                (0xcd, 0x31, 0x31),
+                (0x0d, 0xbc, 0x79),
+                (0xe5, 0xe5, 0x10),
+                (0x24, 0x72, 0xc8),
+                (0xbc, 0x3f, 0xbc),
+                (0x11, 0xa8, 0xcd),
+                (0xe5, 0xe5, 0xe5),
+                (0x66, 0x66, 0x66),
+                (0xf1, 0x4c, 0x4c),
+                (0x23, 0xd1, 0x8b),
+                (0xf5, 0xf5, 0x43),
+                (0x3b, 0x8e, 0xea),
+                (0xd6, 0x70, 0xd6),
+                (0x29, 0xb8, 0xdb),
+                (0xff, 0xff, 0xff),
+            ],
+            foreground: (0xcc, 0xcc, 0xcc),
+            background: (0x1e, 0x1e, 0x1e),
+        }
+    }
+}
+
+impl Palette {
+    /// Creates the default dark palette.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the RGB value used for one of the 16 named colors.
+    ///
+    /// Other colors are left untouched: [`Color::Rgb`] is always used as is, and
+    /// [`Color::Indexed`] only uses the palette for its first 16 entries.
+    pub fn color(mut self, color: Color, rgb: Rgb) -> Self {
+        if let Some(i) = NAMED_COLORS.iter().position(|(_, c)| *c == color) {
+            self.colors[i] = rgb;
+        }
+        self
+    }
+
+    /// Sets the color of text without a foreground color.
+    pub fn foreground(mut self, rgb: Rgb) -> Self {
+        self.foreground = rgb;
+        self
+    }
+
+    /// Sets the color behind text without a background color.
+    pub fn background(mut self, rgb: Rgb) -> Self {
+        self.background = rgb;
+        self
+    }
+
+    /// Resolves a color to its RGB value.
+    pub fn rgb(&self, color: Color) -> Rgb {
+        match color {
+            Color::Rgb(r, g, b) => (r, g, b),
+            Color::Indexed(i) => self.indexed(i),
+            Color::ANSI(c @ 30..=37) | Color::ANSI(c @ 40..=47) => self.colors[(c % 10) as usize],
+            Color::ANSI(c @ 90..=97) | Color::ANSI(c @ 100..=107) => {
+                self.colors[(c % 10) as usize + 8]
+            }
+            Color::ANSI(_) => self.foreground,
+            named => {
+                let i = NAMED_COLORS.iter().position(|(_, c)| *c == named);
+                self.colors[i.unwrap_or(7)]
+            }
+        }
+    }
+
+    /// Resolves a color to its `#rrggbb` hex notation.
+    pub fn hex(&self, color: Color) -> String {
+        hex(self.rgb(color))
+    }
+
+    /// Resolves an entry of the 256-color palette.
+    fn indexed(&self, i: u8) -> Rgb {
+        match i {
+            0..=15 => self.colors[i as usize],
+            16..=231 => {
+                let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+                let i = i - 16;
+                (level(i / 36), level(i / 6 % 6), level(i % 6))
+            }
+            _ => {
+                let v = 8 + (i - 232) * 10;
+                (v, v, v)
+            }
+        }
+    }
+
+    /// Returns the foreground and background of `style`, with reverse video applied.
+    fn resolve(&self, style: &Style) -> (Rgb, Option<Rgb>) {
+        let fg = style.fg.map_or(self.foreground, |c| self.rgb(c));
+        let bg = style.bg.map(|c| self.rgb(c));
+        if style.reverse {
+            (bg.unwrap_or(self.background), Some(fg))
+        } else {
+            (fg, bg)
+        }
+    }
+}
+
+fn hex((r, g, b): Rgb) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Escapes text for use in HTML and SVG.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Returns the CSS `text-decoration-line` values of `style`.
+fn decoration_lines(style: &Style) -> Vec<&'static str> {
+    let mut lines = Vec::new();
+    if style.underline.is_some() {
+        lines.push("underline");
+    }
+    if style.strikethrough {
+        lines.push("line-through");
+    }
+    if style.overline {
+        lines.push("overline");
+    }
+    lines
+}
+
+/// Returns the CSS `text-decoration-style` and `text-decoration-color` of the underline.
+fn underline_details(style: &Style, palette: &Palette) -> (Option<&'static str>, Option<String>) {
+    let line = match style.underline {
+        Some(Underline::Double) => Some("double"),
+        Some(Underline::Curly) => Some("wavy"),
+        Some(Underline::Dotted) => Some("dotted"),
+        _ => None,
+    };
+    let color = style
+        .underline_color
+        .filter(|_| style.underline.is_some())
+        .map(|c| palette.hex(c));
+    (line, color)
+}
+
+/// Returns the full CSS `text-decoration` value of `style`, if any.
+fn decoration(style: &Style, palette: &Palette) -> Option<String> {
+    let lines = decoration_lines(style);
+    if lines.is_empty() {
+        return None;
+    }
+    let (line, color) = underline_details(style, palette);
+    let mut decoration = lines.join(" ");
+    for detail in [line.map(String::from), color].into_iter().flatten() {
+        decoration.push(' ');
+        decoration.push_str(&detail);
+    }
+    Some(decoration)
+}
+
+/// Returns the CSS `opacity` of `style`: dim text is translucent, hidden text invisible.
+fn opacity(style: &Style) -> Option<&'static str> {
+    if style.hidden {
+        Some("0")
+    } else if style.dim {
+        Some("0.5")
+    } else {
+        None
+    }
+}
+
+/// Renders styled text to HTML.
+///
+/// The text is wrapped in a `<pre>` element, each span in a `<span>`. Styles are either written
+/// inline or, after calling [`Html::classes`], as CSS classes whose definitions are returned by
+/// [`Html::stylesheet`]. Hyperlinks become `<a>` elements. Blinking is not rendered.
+///
+/// # Example
+///
+/// ```rust
+/// use console_utils::styled::{markup, Html};
+///
+/// let text = markup("[bold red]Error:[/] file missing").unwrap();
+/// let html = Html::new().render(&text);
+/// assert!(html.contains(r#"<span style="color:#cd3131;font-weight:bold">Error:</span>"#));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Html {
+    palette: Palette,
+    class_prefix: Option<String>,
+}
+
+impl Html {
+    /// Creates a renderer writing inline styles with the default palette.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the palette colors are resolved with.
+    pub fn palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Uses CSS classes starting with `prefix` instead of inline styles.
+    ///
+    /// The named colors and attributes get classes like `{prefix}-fg-bright-black`,
+    /// `{prefix}-bg-red` or `{prefix}-bold`. [`Color::Rgb`] and [`Color::Indexed`] have no
+    /// class and are still written inline, as is reverse video.
+    pub fn classes(mut self, prefix: &str) -> Self {
+        self.class_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Returns the CSS rules for the classes used with [`Html::classes`].
+    pub fn stylesheet(&self) -> String {
+        let p = self.class_prefix.as_deref().unwrap_or("term");
+        let mut css = format!(
+            ".{p} {{ color: {}; background-color: {}; }}\n",
+            hex(self.palette.foreground),
+            hex(self.palette.background)
+        );
+        for (name, color) in NAMED_COLORS {
+            let value = self.palette.hex(color);
+            let _ = writeln!(css, ".{p}-fg-{name} {{ color: {value}; }}");
+            let _ = writeln!(css, ".{p}-bg-{name} {{ background-color: {value}; }}");
+        }
+        css.push_str(&format!(
+            ".{p}-bold {{ font-weight: bold; }}\n\
+             .{p}-italic {{ font-style: italic; }}\n\
+             .{p}-dim {{ opacity: 0.5; }}\n\
+             .{p}-hidden {{ opacity: 0; }}\n"
+        ));
+        // every combination of lines needs its own rule, as they share one property
+        let lines = ["underline", "line-through", "overline"];
+        for mask in 1..8 {
+            let used: Vec<_> = (0..3).filter(|i| mask & (1 << i) != 0).collect();
+            let selector: String = used.iter().map(|&i| format!(".{p}-{}", lines[i])).collect();
+            let value: Vec<_> = used.iter().map(|&i| lines[i]).collect();
+            let _ = writeln!(
+                css,
+                "{selector} {{ text-decoration-line: {}; }}",
+                value.join(" ")
+            );
+        }
+        css
+    }
+
+    /// Renders `text` into a `<pre>` element.
+    pub fn render(&self, text: &StyledString) -> String {
+        let mut out = match &self.class_prefix {
+            Some(p) => format!(r#"<pre class="{p}">"#),
+            None => format!(
+                r#"<pre style="color:{};background-color:{}">"#,
+                hex(self.palette.foreground),
+                hex(self.palette.background)
+            ),
+        };
+        for span in text.spans() {
+            let style = span.style();
+            let (classes, css) = self.attributes(&style);
+            let text = escape(span.text());
+            if let Some(link) = span.hyperlink() {
+                let _ = write!(out, r#"<a href="{}">"#, escape(link.url()));
+            }
+            match (classes.is_empty(), css.is_empty()) {
+                (true, true) => out.push_str(&text),
+                (false, true) => {
+                    let _ = write!(out, r#"<span class="{classes}">{text}</span>"#);
+                }
+                (true, false) => {
+                    let _ = write!(out, r#"<span style="{css}">{text}</span>"#);
+                }
+                (false, false) => {
+                    let _ = write!(
+                        out,
+                        r#"<span class="{classes}" style="{css}">{text}</span>"#
+                    );
+                }
+            }
+            if span.hyperlink().is_some() {
+                out.push_str("</a>");
+            }
+        }
+        out.push_str("</pre>");
+        out
+    }
+
+    /// Returns the classes and inline CSS of a span.
+    fn attributes(&self, style: &Style) -> (String, String) {
+        let mut classes = Vec::new();
+        let mut css = Vec::new();
+        let prefix = self.class_prefix.as_deref();
+
+        if style.reverse {
+            let (fg, bg) = self.palette.resolve(style);
+            css.push(format!("color:{}", hex(fg)));
+            if let Some(bg) = bg {
+                css.push(format!("background-color:{}", hex(bg)));
+            }
+        } else {
+            for (color, kind, property) in [
+                (style.fg, "fg", "color"),
+                (style.bg, "bg", "background-color"),
+            ] {
+                match (color, prefix) {
+                    (Some(c), Some(p)) => match NAMED_COLORS.iter().find(|(_, n)| *n == c) {
+                        Some((name, _)) => classes.push(format!("{p}-{kind}-{name}")),
+                        None => css.push(format!("{property}:{}", self.palette.hex(c))),
+                    },
+                    (Some(c), _) => css.push(format!("{property}:{}", self.palette.hex(c))),
+                    (None, _) => {}
+                }
+            }
+        }
+
+        match prefix {
+            Some(p) => {
+                for (enabled, name) in [
+                    (style.bold, "bold"),
+                    (style.italic, "italic"),
+                    (style.dim && !style.hidden, "dim"),
+                    (style.hidden, "hidden"),
+                ] {
+                    if enabled {
+                        classes.push(format!("{p}-{name}"));
+                    }
+                }
+                for line in decoration_lines(style) {
+                    classes.push(format!("{p}-{line}"));
+                }
+                let (line, color) = underline_details(style, &self.palette);
+                if let Some(line) = line {
+                    css.push(format!("text-decoration-style:{line}"));
+                }
+                if let Some(color) = color {
+                    css.push(format!("text-decoration-color:{color}"));
+                }
+            }
+            None => {
+                if style.bold {
+                    css.push("font-weight:bold".into());
+                }
+                if style.italic {
+                    css.push("font-style:italic".into());
+                }
+                if let Some(opacity) = opacity(style) {
+                    css.push(format!("opacity:{opacity}"));
+                }
+                if let Some(decoration) = decoration(style, &self.palette) {
+                    css.push(format!("text-decoration:{decoration}"));
+                }
+            }
+        }
+        (classes.join(" "), css.join(";"))
+    }
+}
+
+/// Renders styled text to a standalone SVG image resembling a terminal window.
+///
+/// Every character is placed on a fixed grid of terminal cells, wide characters take two of
+/// them. Blinking is not rendered.
+///
+/// # Example
+///
+/// ```rust
+/// use console_utils::styled::{markup, Svg};
+///
+/// let text = markup("[green]Compiling[/] console-utils").unwrap();
+/// let svg = Svg::new().title("cargo build").render(&text);
+/// assert!(svg.starts_with("<svg"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Svg {
+    palette: Palette,
+    title: Option<String>,
+    font_size: f64,
+    font_family: String,
+    columns: Option<usize>,
+}
+
+impl Default for Svg {
+    fn default() -> Self {
+        Self {
+            palette: Palette::default(),
+            title: None,
+            font_size: 14.0,
+            font_family: "ui-monospace, Menlo, Consolas, monospace".into(),
+            columns: None,
+        }
+    }
+}
+
+impl Svg {
+    /// Padding around the text in font sizes.
+    const PADDING: f64 = 1.0;
+    /// Height of the window bar in font sizes.
+    const BAR: f64 = 2.0;
+
+    /// Creates a renderer with the default palette and a 14px font.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the palette colors are resolved with.
+    pub fn palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Shows `title` in the window bar.
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the font size in pixels.
+    pub fn font_size(mut self, size: f64) -> Self {
+        self.font_size = size;
+        self
+    }
+
+    /// Sets the CSS font family, which should be monospaced.
+    pub fn font_family(mut self, family: &str) -> Self {
+        self.font_family = family.into();
+        self
+    }
+
+    /// Sets the width of the terminal in cells, defaults to the widest line.
+    pub fn columns(mut self, columns: usize) -> Self {
+        self.columns = Some(columns);
+        self
+    }
+
+    /// Renders `text` into an SVG document.
+    pub fn render(&self, text: &StyledString) -> String {
+        let cell_w = self.font_size * 0.6;
+        let line_h = self.font_size * 1.25;
+        let pad = self.font_size * Self::PADDING;
+        let bar = self.font_size * Self::BAR;
+
+        let lines = split_lines(text);
+        let columns = self.columns.unwrap_or_else(|| {
+            lines
+                .iter()
+                .map(|line| line.iter().map(|(t, _)| display_width(t)).sum())
+                .max()
+                .unwrap_or(0)
+        });
+        let width = columns as f64 * cell_w + 2.0 * pad;
+        let height = lines.len() as f64 * line_h + 2.0 * pad + bar;
+
+        let mut out = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width:.1}" height="{height:.1}" viewBox="0 0 {width:.1} {height:.1}" font-family="{}" font-size="{}">"#,
+            escape(&self.font_family),
+            self.font_size
+        );
+        let _ = write!(
+            out,
+            r#"<rect width="100%" height="100%" rx="{:.1}" fill="{}"/>"#,
+            self.font_size * 0.5,
+            hex(self.palette.background)
+        );
+        for (i, color) in ["#ff5f56", "#ffbd2e", "#27c93f"].iter().enumerate() {
+            let _ = write!(
+                out,
+                r#"<circle cx="{:.1}" cy="{:.1}" r="{:.1}" fill="{color}"/>"#,
+                pad + i as f64 * self.font_size * 1.2,
+                bar / 2.0,
+                self.font_size * 0.4
+            );
+        }
+        if let Some(title) = &self.title {
+            let _ = write!(
+                out,
+                r#"<text x="{:.1}" y="{:.1}" text-anchor="middle" dominant-baseline="middle" fill="{}">{}</text>"#,
+                width / 2.0,
+                bar / 2.0,
+                hex(self.palette.foreground),
+                escape(title)
+            );
+        }
+
+        for (row, line) in lines.iter().enumerate() {
+            let top = bar + pad + row as f64 * line_h;
+            let baseline = top + self.font_size;
+            let mut column = 0;
+            let mut spans = String::new();
+            for (text, style) in line {
+                let x = pad + column as f64 * cell_w;
+                // every grapheme is placed on its own cell, as the advance of wide characters and
+                // fallback glyphs rarely matches the grid
+                let mut xs = Vec::new();
+                let mut cells = 0;
+                for grapheme in text.graphemes(true) {
+                    let x = pad + (column + cells) as f64 * cell_w;
+                    xs.extend(grapheme.chars().map(|_| format!("{x:.1}")));
+                    cells += grapheme_width(grapheme);
+                }
+                let (fg, bg) = self.palette.resolve(style);
+                if let Some(bg) = bg {
+                    let _ = write!(
+                        out,
+                        r#"<rect x="{x:.1}" y="{top:.1}" width="{:.1}" height="{line_h:.1}" fill="{}"/>"#,
+                        cells as f64 * cell_w,
+                        hex(bg)
+                    );
+                }
+                let _ = write!(
+                    spans,
+                    r#"<tspan x="{}" fill="{}"{}{}{}{}>{}</tspan>"#,
+                    xs.join(" "),
+                    hex(fg),
+                    if style.bold {
+                        r#" font-weight="bold""#
+                    } else {
+                        ""
+                    },
+                    if style.italic {
+                        r#" font-style="italic""#
+                    } else {
+                        ""
+                    },
+                    decoration(style, &self.palette)
+                        .map(|d| format!(r#" text-decoration="{d}""#))
+                        .unwrap_or_default(),
+                    opacity(style)
+                        .map(|o| format!(r#" opacity="{o}""#))
+                        .unwrap_or_default(),
+                    escape(text)
+                );
+                column += cells;
+            }
+            if !spans.is_empty() {
+                let _ = write!(
+                    out,
+                    r#"<text y="{baseline:.1}" xml:space="preserve">{spans}</text>"#
+                );
+            }
+        }
+        out.push_str("</svg>");
+        out
+    }
+}
+
+/// Splits styled text into lines of (text, style) pieces.
+fn split_lines(text: &StyledString) -> Vec<Vec<(&str, Style)>> {
+    let mut lines = vec![Vec::new()];
+    for span in text.spans() {
+        let style = span.style();
+        for (i, part) in span.text().split('\n').enumerate() {
+            if i > 0 {
+                lines.push(Vec::new());
+            }
+            if !part.is_empty() {
+                lines.last_mut().unwrap().push((part, style));
+            }
+        }
+    }
+    lines
+}
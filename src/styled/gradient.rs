@@ -0,0 +1,162 @@
+//! Gradients
+//!
+//! Colors text with a horizontal gradient, interpolating the foreground color per grapheme.
+//! The colors are 24-bit [`Color::Rgb`]s, which are downgraded to the closest supported color
+//! on terminals with fewer colors, see [`Capabilities`](super::Capabilities).
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::{ansi::grapheme_width, Color, Palette, StyledString, StyledText};
+
+/// A horizontal color gradient through a list of color stops.
+///
+/// The stops are spread evenly over the width of the text. All lines of multi-line text share
+/// the same gradient, so characters in the same column get the same color.
+///
+/// # Example
+///
+/// ```rust
+/// use console_utils::styled::{Color, Gradient};
+///
+/// let banner = Gradient::new(&[Color::Rgb(255, 0, 128), Color::Rgb(0, 128, 255)])
+///     .paint("Welcome\nto the setup");
+/// println!("{banner}");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gradient {
+    stops: Vec<(u8, u8, u8)>,
+}
+
+impl Gradient {
+    /// Creates a gradient through the given colors, from left to right.
+    ///
+    /// Named colors are resolved with the default [`Palette`]. Without any stops the text isn't
+    /// colored at all.
+    pub fn new(stops: &[Color]) -> Self {
+        let palette = Palette::default();
+        Self {
+            stops: stops.iter().map(|c| palette.rgb(*c)).collect(),
+        }
+    }
+
+    /// The colors of the rainbow, from red to violet.
+    pub fn rainbow() -> Self {
+        Self::new(&[
+            Color::Rgb(255, 0, 0),
+            Color::Rgb(255, 127, 0),
+            Color::Rgb(255, 255, 0),
+            Color::Rgb(0, 255, 0),
+            Color::Rgb(0, 127, 255),
+            Color::Rgb(75, 0, 130),
+            Color::Rgb(148, 0, 211),
+        ])
+    }
+
+    /// A softer, pastel version of [`Gradient::rainbow`].
+    pub fn pastel() -> Self {
+        Self::new(&[
+            Color::Rgb(255, 179, 186),
+            Color::Rgb(255, 223, 186),
+            Color::Rgb(255, 255, 186),
+            Color::Rgb(186, 255, 201),
+            Color::Rgb(186, 225, 255),
+            Color::Rgb(218, 186, 255),
+        ])
+    }
+
+    /// From purple over pink to orange.
+    pub fn sunset() -> Self {
+        Self::new(&[
+            Color::Rgb(94, 44, 165),
+            Color::Rgb(231, 60, 126),
+            Color::Rgb(255, 160, 60),
+        ])
+    }
+
+    /// Returns the color at `t`, where `0.0` is the first and `1.0` the last stop.
+    pub fn at(&self, t: f64) -> Option<Color> {
+        let (first, last) = (self.stops.first()?, self.stops.last()?);
+        let t = if t.is_finite() {
+            t.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        if self.stops.len() == 1 || t >= 1.0 {
+            let (r, g, b) = if t >= 1.0 { *last } else { *first };
+            return Some(Color::Rgb(r, g, b));
+        }
+
+        let position = t * (self.stops.len() - 1) as f64;
+        let i = position.floor() as usize;
+        let local = position - i as f64;
+        let (from, to) = (self.stops[i], self.stops[i + 1]);
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * local).round() as u8;
+        Some(Color::Rgb(
+            mix(from.0, to.0),
+            mix(from.1, to.1),
+            mix(from.2, to.2),
+        ))
+    }
+
+    /// Colors `text` with the gradient.
+    pub fn paint(&self, text: &str) -> StyledString {
+        self.apply(&StyledText::new(text).into())
+    }
+
+    /// Replaces the foreground color of already styled text with the gradient, keeping all other
+    /// attributes of its spans.
+    pub fn apply(&self, text: &StyledString) -> StyledString {
+        let width = text
+            .plain_text()
+            .split('\n')
+            .map(|line| line.graphemes(true).map(grapheme_width).sum::<usize>())
+            .max()
+            .unwrap_or(0);
+        let scale = width.saturating_sub(1).max(1) as f64;
+
+        let mut out = StyledString::new();
+        let mut column = 0;
+        for span in text.spans() {
+            // the pieces keep the link of their span
+            let piece = |grapheme: &str, style| {
+                StyledText {
+                    link: span.link.clone(),
+                    ..StyledText::new(grapheme).with_style(style)
+                }
+                .into_owned()
+            };
+            for grapheme in span.text().graphemes(true) {
+                if grapheme.contains('\n') {
+                    out.push(piece(grapheme, span.style()));
+                    column = 0;
+                    continue;
+                }
+                let style = match self.at(column as f64 / scale) {
+                    Some(color) => span.style().fg(color),
+                    None => span.style(),
+                };
+                out.push(piece(grapheme, style));
+                column += grapheme_width(grapheme);
+            }
+        }
+        out
+    }
+}
+
+/// Colors `text` with a horizontal gradient through `stops`, see [`Gradient`].
+///
+/// # Example
+///
+/// ```rust
+/// use console_utils::styled::{gradient, Color};
+///
+/// println!("{}", gradient("Hello World!", &[Color::Red, Color::Blue]));
+/// ```
+pub fn gradient(text: &str, stops: &[Color]) -> StyledString {
+    Gradient::new(stops).paint(text)
+}
+
+/// Colors `text` with the colors of the rainbow, see [`Gradient::rainbow`].
+pub fn rainbow(text: &str) -> StyledString {
+    Gradient::rainbow().paint(text)
+}
@@ -0,0 +1,559 @@
+//! Inline Markup
+//!
+//! A small markup language for writing styled text in a single string, similar to BBCode:
+//! `[bold red]Error:[/] file [underline]{}[/] missing`.
+//!
+//! The tokenizer and the tag parser are `const fn`s, so the [`markup!`](crate::markup!) macro can
+//! reject malformed markup at compile time with the same rules [`markup()`] applies at runtime.
+
+use std::fmt;
+
+use super::{Color, Style, StyledString, Underline, NAMED_COLORS};
+
+/// Maximum nesting depth of tags validated at compile time by [`markup!`](crate::markup!).
+const MAX_CONST_DEPTH: usize = 32;
+
+/// The different ways markup can be malformed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkupErrorKind {
+    /// A `[` without a matching `]`.
+    UnterminatedTag,
+    /// A tag without any content, `[]`.
+    EmptyTag,
+    /// A word inside a tag that is neither a color nor a formatting attribute.
+    UnknownStyle,
+    /// `on` that isn't followed by a background color.
+    MissingBackground,
+    /// A closing tag without any open tag.
+    UnexpectedClose,
+    /// A named closing tag, like `[/bold]`, that doesn't match the innermost open tag.
+    MismatchedClose,
+    /// An open tag that is never closed.
+    UnclosedTag,
+}
+
+impl MarkupErrorKind {
+    const fn message(self) -> &'static str {
+        match self {
+            MarkupErrorKind::UnterminatedTag => "tag is missing its closing `]`",
+            MarkupErrorKind::EmptyTag => "empty tag, use `[[` to write a literal `[`",
+            MarkupErrorKind::UnknownStyle => "unknown style",
+            MarkupErrorKind::MissingBackground => "`on` must be followed by a background color",
+            MarkupErrorKind::UnexpectedClose => "closing tag without an open tag",
+            MarkupErrorKind::MismatchedClose => "closing tag doesn't match the innermost open tag",
+            MarkupErrorKind::UnclosedTag => "tag is never closed",
+        }
+    }
+}
+
+/// The error returned by [`markup()`] for malformed markup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkupError {
+    kind: MarkupErrorKind,
+    offset: usize,
+    fragment: String,
+}
+
+impl MarkupError {
+    fn new(kind: MarkupErrorKind, source: &str, offset: usize) -> Self {
+        let rest = &source[offset..];
+        let len = match kind {
+            MarkupErrorKind::UnknownStyle | MarkupErrorKind::MissingBackground => rest
+                .find(|c: char| c.is_ascii_whitespace() || c == ']')
+                .unwrap_or(rest.len()),
+            _ => rest.find(']').map_or(rest.len(), |i| i + 1),
+        };
+        Self {
+            kind,
+            offset,
+            fragment: rest[..len].into(),
+        }
+    }
+
+    /// Returns what is wrong with the markup.
+    pub fn kind(&self) -> MarkupErrorKind {
+        self.kind
+    }
+
+    /// Returns the byte offset of the offending tag or word in the markup.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: `{}` at byte {}",
+            self.kind.message(),
+            self.fragment,
+            self.offset
+        )
+    }
+}
+
+impl std::error::Error for MarkupError {}
+
+/// A single piece of markup.
+#[derive(Clone, Copy)]
+enum Token {
+    /// Plain text up to the contained byte offset.
+    Text(usize),
+    /// `[[`, a literal `[`.
+    Escape,
+    /// An opening tag with its content range.
+    Open(usize, usize),
+    /// A closing tag with the range of its (possibly empty) name.
+    Close(usize, usize),
+}
+
+/// Reads the token starting at `i`, returning it together with the offset of the next one.
+const fn next_token(b: &[u8], i: usize) -> Result<(Token, usize), (MarkupErrorKind, usize)> {
+    if b[i] != b'[' {
+        let mut end = i;
+        while end < b.len() && b[end] != b'[' {
+            end += 1;
+        }
+        return Ok((Token::Text(end), end));
+    }
+    if i + 1 < b.len() && b[i + 1] == b'[' {
+        return Ok((Token::Escape, i + 2));
+    }
+    let mut end = i + 1;
+    while end < b.len() && b[end] != b']' {
+        end += 1;
+    }
+    if end == b.len() {
+        return Err((MarkupErrorKind::UnterminatedTag, i));
+    }
+    let (start, stop) = trim(b, i + 1, end);
+    if start < stop && b[start] == b'/' {
+        let (start, stop) = trim(b, start + 1, stop);
+        Ok((Token::Close(start, stop), end + 1))
+    } else if start == stop {
+        Err((MarkupErrorKind::EmptyTag, i))
+    } else {
+        Ok((Token::Open(start, stop), end + 1))
+    }
+}
+
+/// Narrows `start..end` to exclude surrounding whitespace.
+const fn trim(b: &[u8], mut start: usize, mut end: usize) -> (usize, usize) {
+    while start < end && b[start].is_ascii_whitespace() {
+        start += 1;
+    }
+    while end > start && b[end - 1].is_ascii_whitespace() {
+        end -= 1;
+    }
+    (start, end)
+}
+
+/// Compares `b[start..end]` with `lit`.
+const fn eq(b: &[u8], start: usize, end: usize, lit: &[u8]) -> bool {
+    if end - start != lit.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < lit.len() {
+        if b[start + i] != lit[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Compares two ranges of `b`.
+const fn eq_ranges(b: &[u8], a: (usize, usize), other: (usize, usize)) -> bool {
+    if a.1 - a.0 != other.1 - other.0 {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.1 - a.0 {
+        if b[a.0 + i] != b[other.0 + i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn hex_digit(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Parses `#rrggbb` or `#rgb` in `b[start..end]`.
+const fn parse_hex(b: &[u8], start: usize, end: usize) -> Option<Color> {
+    let len = end - start;
+    if (len != 7 && len != 4) || b[start] != b'#' {
+        return None;
+    }
+    let mut rgb = [0u8; 3];
+    let mut i = 0;
+    while i < 3 {
+        if len == 7 {
+            match (
+                hex_digit(b[start + 1 + 2 * i]),
+                hex_digit(b[start + 2 + 2 * i]),
+            ) {
+                (Some(hi), Some(lo)) => rgb[i] = hi * 16 + lo,
+                _ => return None,
+            }
+        } else {
+            match hex_digit(b[start + 1 + i]) {
+                Some(d) => rgb[i] = d * 17,
+                None => return None,
+            }
+        }
+        i += 1;
+    }
+    Some(Color::Rgb(rgb[0], rgb[1], rgb[2]))
+}
+
+/// Parses `ansi(n)`, an index into the 256-color palette, in `b[start..end]`.
+const fn parse_index(b: &[u8], start: usize, end: usize) -> Option<Color> {
+    if end - start < 7 || !eq(b, start, start + 5, b"ansi(") || b[end - 1] != b')' {
+        return None;
+    }
+    let mut index: u16 = 0;
+    let mut i = start + 5;
+    while i < end - 1 {
+        if !b[i].is_ascii_digit() || i - start >= 8 {
+            return None;
+        }
+        index = index * 10 + (b[i] - b'0') as u16;
+        i += 1;
+    }
+    if index > 255 {
+        return None;
+    }
+    Some(Color::Indexed(index as u8))
+}
+
+/// Parses a lowercase color name, hex color or palette index in `b[start..end]`.
+///
+/// Bright colors may be written as `bright-red`, `bright_red` or `brightred`.
+pub(super) const fn parse_color(b: &[u8], start: usize, end: usize) -> Option<Color> {
+    if start < end && b[start] == b'#' {
+        return parse_hex(b, start, end);
+    }
+    if let Some(color) = parse_index(b, start, end) {
+        return Some(color);
+    }
+    let mut i = 0;
+    while i < NAMED_COLORS.len() {
+        let (name, color) = NAMED_COLORS[i];
+        let name = name.as_bytes();
+        if eq(b, start, end, name) {
+            return Some(color);
+        }
+        // `bright-` is 7 bytes long; also accept `bright_` and `bright`
+        if name.len() > 7 && end - start > 6 && eq(b, start, start + 6, b"bright") {
+            let rest = if b[start + 6] == b'_' {
+                start + 7
+            } else {
+                start + 6
+            };
+            if eq_tail(b, rest, end, name, 7) {
+                return Some(color);
+            }
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Compares `b[start..end]` with `lit[from..]`.
+const fn eq_tail(b: &[u8], start: usize, end: usize, lit: &[u8], from: usize) -> bool {
+    if end < start || end - start != lit.len() - from {
+        return false;
+    }
+    let mut i = 0;
+    while i < end - start {
+        if b[start + i] != lit[from + i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Parses the content of an opening tag into the style it applies.
+const fn parse_tag(b: &[u8], start: usize, end: usize) -> Result<Style, (MarkupErrorKind, usize)> {
+    let mut style = Style::new();
+    let mut background = false;
+    let mut i = start;
+    while i < end {
+        while i < end && b[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        if i == end {
+            break;
+        }
+        let word = i;
+        while i < end && !b[i].is_ascii_whitespace() {
+            i += 1;
+        }
+
+        if background {
+            match parse_color(b, word, i) {
+                Some(color) => style = style.bg(color),
+                None => return Err((MarkupErrorKind::MissingBackground, word)),
+            }
+            background = false;
+        } else if eq(b, word, i, b"on") {
+            background = true;
+        } else if eq(b, word, i, b"bold") || eq(b, word, i, b"b") {
+            style = style.bold();
+        } else if eq(b, word, i, b"dim") {
+            style = style.dim();
+        } else if eq(b, word, i, b"italic") || eq(b, word, i, b"i") {
+            style = style.italic();
+        } else if eq(b, word, i, b"underline") || eq(b, word, i, b"u") {
+            style = style.underline();
+        } else if eq(b, word, i, b"double-underline") || eq(b, word, i, b"uu") {
+            style = style.underline_style(Underline::Double);
+        } else if eq(b, word, i, b"curly-underline") {
+            style = style.underline_style(Underline::Curly);
+        } else if eq(b, word, i, b"dotted-underline") {
+            style = style.underline_style(Underline::Dotted);
+        } else if eq(b, word, i, b"blink") {
+            style = style.blink();
+        } else if eq(b, word, i, b"reverse") {
+            style = style.reverse();
+        } else if eq(b, word, i, b"hidden") {
+            style = style.hidden();
+        } else if eq(b, word, i, b"overline") {
+            style = style.overline();
+        } else if eq(b, word, i, b"strikethrough")
+            || eq(b, word, i, b"strike")
+            || eq(b, word, i, b"s")
+        {
+            style = style.strikethrough();
+        } else {
+            match parse_color(b, word, i) {
+                Some(color) => style = style.fg(color),
+                None => return Err((MarkupErrorKind::UnknownStyle, word)),
+            }
+        }
+    }
+    if background {
+        return Err((MarkupErrorKind::MissingBackground, end));
+    }
+    Ok(style)
+}
+
+/// Validates markup for the [`markup!`](crate::markup!) macro, panicking (and therefore failing
+/// compilation when evaluated in a constant) on malformed markup or when the number of `{}`
+/// placeholders doesn't match `args`.
+#[doc(hidden)]
+pub const fn __check_markup(source: &str, args: usize) {
+    let b = source.as_bytes();
+    let mut stack = [(0usize, 0usize); MAX_CONST_DEPTH];
+    let mut depth = 0;
+    let mut placeholders = 0;
+    let mut i = 0;
+    while i < b.len() {
+        let (token, next) = match next_token(b, i) {
+            Ok(t) => t,
+            Err((kind, _)) => panic!("{}", kind.message()),
+        };
+        match token {
+            Token::Text(end) => {
+                let mut j = i;
+                while j < end {
+                    if b[j] == b'{' || b[j] == b'}' {
+                        if j + 1 < end && b[j + 1] == b[j] {
+                            j += 1;
+                        } else if b[j] == b'{' && j + 1 < end && b[j + 1] == b'}' {
+                            placeholders += 1;
+                            j += 1;
+                        } else {
+                            panic!(
+                                "unmatched brace, use `{{{{` or `}}}}` to write a literal brace"
+                            );
+                        }
+                    }
+                    j += 1;
+                }
+            }
+            Token::Escape => {}
+            Token::Open(start, end) => {
+                if let Err((kind, _)) = parse_tag(b, start, end) {
+                    panic!("{}", kind.message());
+                }
+                if depth == MAX_CONST_DEPTH {
+                    panic!("tags are nested too deeply to be checked at compile time");
+                }
+                stack[depth] = (start, end);
+                depth += 1;
+            }
+            Token::Close(start, end) => {
+                if depth == 0 {
+                    panic!("{}", MarkupErrorKind::UnexpectedClose.message());
+                }
+                depth -= 1;
+                if start != end && !eq_ranges(b, (start, end), stack[depth]) {
+                    panic!("{}", MarkupErrorKind::MismatchedClose.message());
+                }
+            }
+        }
+        i = next;
+    }
+    if depth > 0 {
+        panic!("{}", MarkupErrorKind::UnclosedTag.message());
+    }
+    if placeholders != args {
+        panic!("the number of `{{}}` placeholders doesn't match the number of arguments");
+    }
+}
+
+/// Parses markup whose `{}` placeholders are replaced by `args`, used by [`markup!`](crate::markup!).
+#[doc(hidden)]
+pub fn __markup_with_args(source: &str, args: &[&dyn fmt::Display]) -> StyledString {
+    parse(source, Some(args)).expect("markup is validated at compile time")
+}
+
+/// Parses inline markup into a [`StyledString`].
+///
+/// Tags are written in square brackets and contain whitespace separated words:
+/// - formatting attributes: `bold` (`b`), `dim`, `italic` (`i`), `underline` (`u`),
+///   `double-underline` (`uu`), `curly-underline`, `dotted-underline`, `blink`, `reverse`,
+///   `hidden`, `strikethrough` (`strike`, `s`) and `overline`
+/// - foreground colors: names like `red` or `bright-black`, hex colors like `#ff8800` and
+///   256-color palette indices like `ansi(208)`
+/// - background colors: `on` followed by a color, like `on blue`
+///
+/// `[/]` closes the innermost open tag; a named closing tag like `[/bold red]` must repeat its
+/// content exactly. Tags can be nested, inner tags inherit the style of the outer ones. Write
+/// `[[` for a literal `[`.
+///
+/// Use the [`markup!`](crate::markup!) macro to check the markup at compile time and to insert
+/// values into it.
+///
+/// # Example
+///
+/// ```rust
+/// use console_utils::styled::markup;
+///
+/// let text = markup("[bold red]Error:[/] file [underline]config.toml[/] missing").unwrap();
+/// println!("{text}");
+///
+/// assert!(markup("[bold]unclosed").is_err());
+/// ```
+pub fn markup(source: &str) -> Result<StyledString, MarkupError> {
+    parse(source, None)
+}
+
+/// Parses inline markup at compile time.
+///
+/// Works like [`markup()`], but malformed markup is a compile error instead of a runtime one.
+/// Additional arguments replace `{}` placeholders in the markup, similar to [`format!`]. They are
+/// inserted as plain text, so brackets in them are never interpreted as tags. Write `{{` and
+/// `}}` for literal braces.
+///
+/// # Example
+///
+/// ```rust
+/// use console_utils::markup;
+///
+/// let file = "config.toml";
+/// let text = markup!("[bold red]Error:[/] file [underline]{}[/] missing", file);
+/// println!("{text}");
+/// ```
+///
+/// Malformed markup doesn't compile:
+///
+/// ```rust,compile_fail
+/// let text = console_utils::markup!("[bold]never closed");
+/// ```
+#[macro_export]
+macro_rules! markup {
+    ($markup:literal $(, $arg:expr)* $(,)?) => {{
+        const _: () = $crate::styled::__check_markup(
+            $markup,
+            <[&str]>::len(&[$(stringify!($arg)),*]),
+        );
+        $crate::styled::__markup_with_args($markup, &[$(&$arg as &dyn ::std::fmt::Display),*])
+    }};
+}
+
+/// Parses the markup; `args` replace `{}` placeholders if given.
+fn parse(source: &str, args: Option<&[&dyn fmt::Display]>) -> Result<StyledString, MarkupError> {
+    let b = source.as_bytes();
+    let mut out = StyledString::new();
+    // (offset of the `[`, content range, resulting style)
+    let mut stack: Vec<(usize, (usize, usize), Style)> = Vec::new();
+    let mut next_arg = 0;
+    let mut i = 0;
+
+    while i < b.len() {
+        let current = stack.last().map_or(Style::new(), |t| t.2);
+        let (token, next) =
+            next_token(b, i).map_err(|(kind, offset)| MarkupError::new(kind, source, offset))?;
+        match token {
+            Token::Text(end) => match args {
+                Some(args) => {
+                    let text = &source[i..end];
+                    let mut chunk = String::new();
+                    let mut chars = text.chars().peekable();
+                    while let Some(c) = chars.next() {
+                        match (c, chars.peek()) {
+                            ('{', Some('}')) => {
+                                chars.next();
+                                chunk.push_str(&args[next_arg].to_string());
+                                next_arg += 1;
+                            }
+                            ('{', Some('{')) | ('}', Some('}')) => {
+                                chars.next();
+                                chunk.push(c);
+                            }
+                            _ => chunk.push(c),
+                        }
+                    }
+                    out.push_str(&chunk, current);
+                }
+                None => out.push_str(&source[i..end], current),
+            },
+            Token::Escape => out.push_str("[", current),
+            Token::Open(start, end) => {
+                let tag = parse_tag(b, start, end)
+                    .map_err(|(kind, offset)| MarkupError::new(kind, source, offset))?;
+                stack.push((i, (start, end), current.patch(tag)));
+            }
+            Token::Close(start, end) => match stack.pop() {
+                None => {
+                    return Err(MarkupError::new(
+                        MarkupErrorKind::UnexpectedClose,
+                        source,
+                        i,
+                    ))
+                }
+                Some((_, open, _)) if start != end && !eq_ranges(b, (start, end), open) => {
+                    return Err(MarkupError::new(
+                        MarkupErrorKind::MismatchedClose,
+                        source,
+                        i,
+                    ))
+                }
+                Some(_) => {}
+            },
+        }
+        i = next;
+    }
+
+    match stack.pop() {
+        Some((offset, _, _)) => Err(MarkupError::new(
+            MarkupErrorKind::UnclosedTag,
+            source,
+            offset,
+        )),
+        None => Ok(out),
+    }
+}
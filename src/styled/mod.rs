@@ -0,0 +1,974 @@
+//! Style Utilities
+//!
+//! This module provides functions for coloring text using ANSI escape code sequences.
+//! It allows setting foreground and background colors, as well as applying bold, dim, italic,
+//! underline (in several line styles and colors), blink, reverse, hidden, strikethrough and
+//! overline formatting, as well as turning text into hyperlinks. Attributes not every terminal understands fall back to supported ones
+//! based on the detected [`Capabilities`].
+//!
+//! Longer pieces of text made out of differently styled fragments can be built as a
+//! [`StyledString`], either by pushing [`StyledText`] spans or by parsing inline markup with
+//! [`markup()`] and the [`markup!`](crate::markup!) macro, or from already formatted output with
+//! [`parse_ansi`]. Such strings can be exported to HTML and SVG using [`Html`] and [`Svg`],
+//! colored per character with a [`Gradient`], and wrapped to the width of the terminal with
+//! [`wrap()`]. With the `serde` feature, whole data structures can be pretty-printed with
+//! `pretty()`.
+
+use std::{borrow::Cow, fmt, str::FromStr};
+
+mod ansi;
+mod export;
+mod gradient;
+mod markup;
+#[cfg(feature = "serde")]
+mod pretty;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod support;
+mod wrap;
+
+pub use ansi::{display_width, parse_ansi, strip_ansi, truncate_to_width};
+pub(crate) use ansi::{grapheme_width, segments, Segment};
+pub use export::{Html, Palette, Svg};
+pub use gradient::{gradient, rainbow, Gradient};
+pub use markup::{markup, MarkupError, MarkupErrorKind};
+#[cfg(feature = "serde")]
+pub use pretty::{pretty, Pretty, PrettyError};
+pub use support::{capabilities, set_capabilities, Capabilities, ColorLevel};
+pub use wrap::{wrap, Wrap};
+
+#[doc(hidden)]
+pub use markup::{__check_markup, __markup_with_args};
+
+/// Represents all colors the text/background can be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    /// Black color.
+    Black,
+    /// Red color.
+    Red,
+    /// Green color.
+    Green,
+    /// Yellow color.
+    Yellow,
+    /// Blue color.
+    Blue,
+    /// Magenta color.
+    Magenta,
+    /// Cyan color.
+    Cyan,
+    /// White color.
+    White,
+    /// Bright Black color.
+    BrightBlack,
+    /// Bright Red color.
+    BrightRed,
+    /// Bright Green color.
+    BrightGreen,
+    /// Bright Yellow color.
+    BrightYellow,
+    /// Bright Blue color.
+    BrightBlue,
+    /// Bright Magenta color.
+    BrightMagenta,
+    /// Bright Cyan color.
+    BrightCyan,
+    /// Bright White color.
+    BrightWhite,
+    /// An ANSI color of your choice.
+    ANSI(u8),
+    /// A color of the 256-color palette given by its index.
+    Indexed(u8),
+    /// A 24-bit color given by its red, green and blue components.
+    Rgb(u8, u8, u8),
+}
+
+/// The 16 named colors in the order of their palette indices, with the names the markup,
+/// `FromStr`, the configuration and the HTML classes use for them.
+const NAMED_COLORS: [(&str, Color); 16] = [
+    ("black", Color::Black),
+    ("red", Color::Red),
+    ("green", Color::Green),
+    ("yellow", Color::Yellow),
+    ("blue", Color::Blue),
+    ("magenta", Color::Magenta),
+    ("cyan", Color::Cyan),
+    ("white", Color::White),
+    ("bright-black", Color::BrightBlack),
+    ("bright-red", Color::BrightRed),
+    ("bright-green", Color::BrightGreen),
+    ("bright-yellow", Color::BrightYellow),
+    ("bright-blue", Color::BrightBlue),
+    ("bright-magenta", Color::BrightMagenta),
+    ("bright-cyan", Color::BrightCyan),
+    ("bright-white", Color::BrightWhite),
+];
+
+impl Color {
+    /// Returns the SGR code of a 16-color palette or raw ANSI color.
+    fn code(self) -> Option<u8> {
+        match self {
+            Color::Black => Some(30),
+            Color::Red => Some(31),
+            Color::Green => Some(32),
+            Color::Yellow => Some(33),
+            Color::Blue => Some(34),
+            Color::Magenta => Some(35),
+            Color::Cyan => Some(36),
+            Color::White => Some(37),
+            Color::BrightBlack => Some(90),
+            Color::BrightRed => Some(91),
+            Color::BrightGreen => Some(92),
+            Color::BrightYellow => Some(93),
+            Color::BrightBlue => Some(94),
+            Color::BrightMagenta => Some(95),
+            Color::BrightCyan => Some(96),
+            Color::BrightWhite => Some(97),
+            Color::ANSI(c) => Some(c),
+            Color::Indexed(_) | Color::Rgb(..) => None,
+        }
+    }
+
+    /// Converts a color to its ANSI foreground variant.
+    fn fg_code(self) -> String {
+        match self {
+            Color::Indexed(i) => format!("38;5;{i}"),
+            Color::Rgb(r, g, b) => format!("38;2;{r};{g};{b}"),
+            color => color.code().unwrap_or_default().to_string(),
+        }
+    }
+
+    /// Converts a color to its underline color variant, if it can be expressed as one.
+    fn underline_code(self) -> Option<String> {
+        let index = match self {
+            Color::Rgb(r, g, b) => return Some(format!("58;2;{r};{g};{b}")),
+            Color::Indexed(i) => i,
+            color => match color.code()? {
+                c @ 30..=37 => c - 30,
+                c @ 90..=97 => c - 82,
+                _ => return None,
+            },
+        };
+        Some(format!("58;5;{index}"))
+    }
+
+    /// Converts a color to its ANSI background variant.
+    fn bg_code(self) -> String {
+        match self {
+            Color::Indexed(i) => format!("48;5;{i}"),
+            Color::Rgb(r, g, b) => format!("48;2;{r};{g};{b}"),
+            color => (color.code().unwrap_or_default() + 10).to_string(),
+        }
+    }
+}
+
+/// The error returned when parsing a [`Color`] from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseColorError(String);
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid color `{}`, expected a color name like `red` or `bright-black`, a hex color like `#ff8800` or a palette index like `ansi(208)`",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+/// Parses a color from its name (`red`, `bright-black`, `bright_black`), a hex notation
+/// (`#ff8800` or `#f80`) or an index into the 256-color palette (`ansi(208)`, see
+/// [`Color::Indexed`]). Names are case-insensitive.
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_ascii_lowercase();
+        markup::parse_color(name.as_bytes(), 0, name.len()).ok_or_else(|| ParseColorError(s.into()))
+    }
+}
+
+/// A set of colors and formatting attributes that can be applied to text.
+///
+/// Unlike [`StyledText`] it doesn't own any text and can therefore be reused for multiple
+/// pieces of text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Style {
+    fg: Option<Color>,
+    bg: Option<Color>,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underline: Option<Underline>,
+    underline_color: Option<Color>,
+    blink: bool,
+    reverse: bool,
+    hidden: bool,
+    strikethrough: bool,
+    overline: bool,
+}
+
+impl Style {
+    /// Creates a new `Style` without any colors or formatting.
+    pub const fn new() -> Self {
+        Self {
+            fg: None,
+            bg: None,
+            bold: false,
+            dim: false,
+            italic: false,
+            underline: None,
+            underline_color: None,
+            blink: false,
+            reverse: false,
+            hidden: false,
+            strikethrough: false,
+            overline: false,
+        }
+    }
+
+    /// Sets the foreground color.
+    pub const fn fg(mut self, color: Color) -> Self {
+        self.fg = Some(color);
+        self
+    }
+
+    /// Sets the background color.
+    pub const fn bg(mut self, color: Color) -> Self {
+        self.bg = Some(color);
+        self
+    }
+
+    /// Enables bold formatting.
+    pub const fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    /// Enables dim (faint) formatting.
+    ///
+    /// Terminals without support show the text with normal intensity.
+    pub const fn dim(mut self) -> Self {
+        self.dim = true;
+        self
+    }
+
+    /// Enables italic formatting.
+    pub const fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    /// Enables underline formatting.
+    pub const fn underline(mut self) -> Self {
+        self.underline = Some(Underline::Single);
+        self
+    }
+
+    /// Enables underline formatting with the given line style.
+    ///
+    /// Only [`Underline::Single`] is supported everywhere. On terminals lacking support for the
+    /// other styles, as reported by [`Capabilities::extended_underline`], a single underline is
+    /// printed instead.
+    pub const fn underline_style(mut self, underline: Underline) -> Self {
+        self.underline = Some(underline);
+        self
+    }
+
+    /// Sets the color of the underline, independent of the text color.
+    ///
+    /// Has no effect without an underline. On terminals lacking support, as reported by
+    /// [`Capabilities::extended_underline`], the underline keeps the text color.
+    pub const fn underline_color(mut self, color: Color) -> Self {
+        self.underline_color = Some(color);
+        self
+    }
+
+    /// Enables the blink effect.
+    pub const fn blink(mut self) -> Self {
+        self.blink = true;
+        self
+    }
+
+    /// Enables reverse video (inverts foreground and background colors).
+    pub const fn reverse(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+
+    /// Hides the text while keeping the space it occupies.
+    ///
+    /// Terminals without support show the text, so don't rely on this to conceal secrets.
+    pub const fn hidden(mut self) -> Self {
+        self.hidden = true;
+        self
+    }
+
+    /// Enables strikethrough formatting.
+    pub const fn strikethrough(mut self) -> Self {
+        self.strikethrough = true;
+        self
+    }
+
+    /// Draws a line above the text.
+    ///
+    /// Terminals without support show the text without the line.
+    pub const fn overline(mut self) -> Self {
+        self.overline = true;
+        self
+    }
+
+    /// Returns the foreground color, if any.
+    pub fn foreground(&self) -> Option<Color> {
+        self.fg
+    }
+
+    /// Returns the background color, if any.
+    pub fn background(&self) -> Option<Color> {
+        self.bg
+    }
+
+    /// Returns whether bold formatting is enabled.
+    pub fn is_bold(&self) -> bool {
+        self.bold
+    }
+
+    /// Returns whether dim formatting is enabled.
+    pub fn is_dim(&self) -> bool {
+        self.dim
+    }
+
+    /// Returns whether italic formatting is enabled.
+    pub fn is_italic(&self) -> bool {
+        self.italic
+    }
+
+    /// Returns whether underline formatting, of any line style, is enabled.
+    pub fn is_underline(&self) -> bool {
+        self.underline.is_some()
+    }
+
+    /// Returns the line style of the underline, if any.
+    pub fn underline_kind(&self) -> Option<Underline> {
+        self.underline
+    }
+
+    /// Returns the color of the underline, if any.
+    pub fn line_color(&self) -> Option<Color> {
+        self.underline_color
+    }
+
+    /// Returns whether the blink effect is enabled.
+    pub fn is_blink(&self) -> bool {
+        self.blink
+    }
+
+    /// Returns whether reverse video is enabled.
+    pub fn is_reverse(&self) -> bool {
+        self.reverse
+    }
+
+    /// Returns whether the text is hidden.
+    pub fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+
+    /// Returns whether strikethrough formatting is enabled.
+    pub fn is_strikethrough(&self) -> bool {
+        self.strikethrough
+    }
+
+    /// Returns whether the text is overlined.
+    pub fn is_overline(&self) -> bool {
+        self.overline
+    }
+
+    /// Returns `true` if neither colors nor formatting are set.
+    pub fn is_plain(&self) -> bool {
+        *self == Style::new()
+    }
+
+    /// Layers `other` on top of this style.
+    ///
+    /// Colors and underline styles set in `other` replace the ones of `self`, formatting
+    /// attributes of both are combined. This is how nested markup tags inherit the style of their
+    /// parents.
+    pub const fn patch(self, other: Style) -> Style {
+        Style {
+            fg: match other.fg {
+                Some(c) => Some(c),
+                None => self.fg,
+            },
+            bg: match other.bg {
+                Some(c) => Some(c),
+                None => self.bg,
+            },
+            bold: self.bold || other.bold,
+            dim: self.dim || other.dim,
+            italic: self.italic || other.italic,
+            underline: match other.underline {
+                Some(u) => Some(u),
+                None => self.underline,
+            },
+            underline_color: match other.underline_color {
+                Some(c) => Some(c),
+                None => self.underline_color,
+            },
+            blink: self.blink || other.blink,
+            reverse: self.reverse || other.reverse,
+            hidden: self.hidden || other.hidden,
+            strikethrough: self.strikethrough || other.strikethrough,
+            overline: self.overline || other.overline,
+        }
+    }
+
+    /// Applies this style to `text`.
+    pub fn paint(self, text: &str) -> StyledText<'_> {
+        StyledText::new(text).with_style(self)
+    }
+
+    /// Returns the SGR parameters of this style for a terminal with the given capabilities,
+    /// separated by `;`.
+    fn sgr_params(&self, caps: &Capabilities) -> String {
+        let mut codes = Vec::new();
+        if let Some(fg) = self.fg.and_then(|c| c.downgrade(caps.colors)) {
+            codes.push(fg.fg_code());
+        }
+        if let Some(bg) = self.bg.and_then(|c| c.downgrade(caps.colors)) {
+            codes.push(bg.bg_code());
+        }
+        if self.bold {
+            codes.push("1".into());
+        }
+        if self.dim {
+            codes.push("2".into());
+        }
+        if self.italic {
+            codes.push("3".into());
+        }
+        if let Some(underline) = self.underline {
+            let code = match underline {
+                _ if !caps.extended_underline => "4",
+                Underline::Single => "4",
+                Underline::Double => "21",
+                Underline::Curly => "4:3",
+                Underline::Dotted => "4:4",
+            };
+            codes.push(code.into());
+            if let Some(code) = self
+                .underline_color
+                .and_then(|c| c.downgrade(caps.colors))
+                .and_then(Color::underline_code)
+            {
+                if caps.extended_underline {
+                    codes.push(code);
+                }
+            }
+        }
+        if self.blink {
+            codes.push("5".into());
+        }
+        if self.reverse {
+            codes.push("7".into());
+        }
+        if self.hidden {
+            codes.push("8".into());
+        }
+        if self.strikethrough {
+            codes.push("9".into());
+        }
+        if self.overline {
+            codes.push("53".into());
+        }
+        codes.join(";")
+    }
+}
+
+/// The line styles an underline can be drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum Underline {
+    /// A single straight line, `SGR 4`.
+    Single,
+    /// Two straight lines, `SGR 21`.
+    Double,
+    /// A wavy line, `SGR 4:3`. Commonly used to mark errors.
+    Curly,
+    /// A dotted line, `SGR 4:4`.
+    Dotted,
+}
+
+/// The target of a hyperlink, see [`StyledText::link`].
+///
+/// Control characters are removed from the URL and id, so they can't end the escape sequence
+/// early. The id additionally can't contain `:` and `;`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Link {
+    url: String,
+    id: Option<String>,
+}
+
+impl Link {
+    /// Creates a link to `url`.
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.chars().filter(|c| !c.is_control()).collect(),
+            id: None,
+        }
+    }
+
+    /// Sets the id of the link.
+    ///
+    /// Terminals highlight all text sharing a URL and id together on hover, which connects a
+    /// link split over multiple lines or spans. Without an id, only adjacent cells are connected.
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = Some(
+            id.chars()
+                .filter(|c| !c.is_control() && *c != ':' && *c != ';')
+                .collect(),
+        );
+        self
+    }
+
+    /// Returns the URL the link points to.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns the id of the link.
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// Returns the OSC 8 sequence starting the link.
+    fn open(&self) -> String {
+        match &self.id {
+            Some(id) => format!("\x1B]8;id={};{}\x1B\\", id, self.url),
+            None => format!("\x1B]8;;{}\x1B\\", self.url),
+        }
+    }
+}
+
+impl From<&str> for Link {
+    fn from(url: &str) -> Self {
+        Self::new(url)
+    }
+}
+
+impl From<String> for Link {
+    fn from(url: String) -> Self {
+        Self::new(&url)
+    }
+}
+
+/// The OSC 8 sequence ending a link.
+const LINK_END: &str = "\x1B]8;;\x1B\\";
+
+/// Represents a piece of text with optional color and formatting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyledText<'a> {
+    text: Cow<'a, str>,
+    style: Style,
+    link: Option<Link>,
+}
+
+impl<'a> StyledText<'a> {
+    /// Creates a new `StyledText` instance with default settings.
+    ///
+    /// # Arguments
+    /// * `text` - The string slice representing the text.
+    ///
+    /// # Returns
+    /// A `StyledText` instance with no colors or formatting applied.
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text: Cow::Borrowed(text),
+            style: Style::new(),
+            link: None,
+        }
+    }
+
+    /// Sets the foreground color of the text.
+    ///
+    /// # Arguments
+    /// * `color` - A `Color` enum variant representing the desired foreground color.
+    ///
+    /// # Returns
+    /// The modified `StyledText` instance.
+    pub fn fg(mut self, color: Color) -> Self {
+        self.style = self.style.fg(color);
+        self
+    }
+
+    /// Sets the background color of the text.
+    ///
+    /// # Arguments
+    /// * `color` - A `Color` enum variant representing the desired background color.
+    ///
+    /// # Returns
+    /// The modified `StyledText` instance.
+    pub fn bg(mut self, color: Color) -> Self {
+        self.style = self.style.bg(color);
+        self
+    }
+
+    /// Enables bold formatting for the text.
+    ///
+    /// # Returns
+    /// The modified `StyledText` instance with bold formatting applied.
+    pub fn bold(mut self) -> Self {
+        self.style = self.style.bold();
+        self
+    }
+
+    /// Enables dim (faint) formatting for the text.
+    ///
+    /// Terminals without support show the text with normal intensity.
+    ///
+    /// # Returns
+    /// The modified `StyledText` instance with dim formatting applied.
+    pub fn dim(mut self) -> Self {
+        self.style = self.style.dim();
+        self
+    }
+
+    /// Enables italic formatting for the text.
+    ///
+    /// # Returns
+    /// The modified `StyledText` instance with italic formatting applied.
+    pub fn italic(mut self) -> Self {
+        self.style = self.style.italic();
+        self
+    }
+
+    /// Enables underline formatting for the text.
+    ///
+    /// # Returns
+    /// The modified `StyledText` instance with underline formatting applied.
+    pub fn underline(mut self) -> Self {
+        self.style = self.style.underline();
+        self
+    }
+
+    /// Enables underline formatting with the given line style for the text.
+    ///
+    /// Terminals lacking support for styles other than [`Underline::Single`] print a single
+    /// underline instead.
+    ///
+    /// # Arguments
+    /// * `underline` - The `Underline` line style.
+    ///
+    /// # Returns
+    /// The modified `StyledText` instance with underline formatting applied.
+    pub fn underline_style(mut self, underline: Underline) -> Self {
+        self.style = self.style.underline_style(underline);
+        self
+    }
+
+    /// Sets the color of the underline, independent of the text color.
+    ///
+    /// Has no effect without an underline. Terminals lacking support draw the underline in the
+    /// text color.
+    ///
+    /// # Arguments
+    /// * `color` - A `Color` enum variant representing the desired underline color.
+    ///
+    /// # Returns
+    /// The modified `StyledText` instance.
+    pub fn underline_color(mut self, color: Color) -> Self {
+        self.style = self.style.underline_color(color);
+        self
+    }
+
+    /// Enables blink effect for the text.
+    ///
+    /// # Returns
+    /// The modified `StyledText` instance with blinking enabled.
+    pub fn blink(mut self) -> Self {
+        self.style = self.style.blink();
+        self
+    }
+
+    /// Enables reverse video (inverts foreground and background colors).
+    ///
+    /// # Returns
+    /// The modified `StyledText` instance with inverted colors.
+    pub fn reverse(mut self) -> Self {
+        self.style = self.style.reverse();
+        self
+    }
+
+    /// Hides the text while keeping the space it occupies.
+    ///
+    /// Terminals without support show the text, so don't rely on this to conceal secrets.
+    ///
+    /// # Returns
+    /// The modified `StyledText` instance with hidden text.
+    pub fn hidden(mut self) -> Self {
+        self.style = self.style.hidden();
+        self
+    }
+
+    /// Enables strikethrough formatting for the text.
+    ///
+    /// # Returns
+    /// The modified `StyledText` instance with strikethrough applied.
+    pub fn strikethrough(mut self) -> Self {
+        self.style = self.style.strikethrough();
+        self
+    }
+
+    /// Draws a line above the text.
+    ///
+    /// Terminals without support show the text without the line.
+    ///
+    /// # Returns
+    /// The modified `StyledText` instance with overline applied.
+    pub fn overline(mut self) -> Self {
+        self.style = self.style.overline();
+        self
+    }
+
+    /// Replaces the whole style of the text.
+    ///
+    /// # Arguments
+    /// * `style` - The `Style` to apply.
+    ///
+    /// # Returns
+    /// The modified `StyledText` instance.
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Turns the text into a clickable hyperlink using the OSC 8 escape sequence.
+    ///
+    /// The link is left out when colors are disabled or the terminal is known not to support
+    /// hyperlinks, see [`Capabilities::hyperlinks`], so the plain text is printed instead.
+    ///
+    /// # Arguments
+    /// * `link` - The URL as a string, or a [`Link`] with an id.
+    ///
+    /// # Returns
+    /// The modified `StyledText` instance.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use console_utils::styled::{Link, StyledText};
+    ///
+    /// println!("{}", StyledText::new("docs").underline().link("https://docs.rs"));
+    /// println!("{}", StyledText::new("main.rs").link(Link::new("file:///src/main.rs").with_id("main")));
+    /// ```
+    pub fn link(mut self, link: impl Into<Link>) -> Self {
+        self.link = Some(link.into());
+        self
+    }
+
+    /// Returns the unstyled text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the style applied to the text.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Returns the hyperlink of the text, if any.
+    pub fn hyperlink(&self) -> Option<&Link> {
+        self.link.as_ref()
+    }
+
+    /// Converts the text into an owned `StyledText` that no longer borrows its input.
+    pub fn into_owned(self) -> StyledText<'static> {
+        StyledText {
+            text: Cow::Owned(self.text.into_owned()),
+            style: self.style,
+            link: self.link,
+        }
+    }
+
+    /// Converts the colored text into a formatted ANSI escape sequence string.
+    ///
+    /// All attributes are emitted as they are, regardless of the terminal. Printing the text
+    /// with `Display` instead applies the fallbacks for the [`capabilities`] of the terminal.
+    ///
+    /// # Returns
+    /// A `String` containing the ANSI-formatted text.
+    pub fn format_sequence(&self) -> String {
+        self.format_for(&Capabilities::all())
+    }
+
+    /// Converts the text into a formatted ANSI escape sequence string for a terminal with the
+    /// given capabilities, applying fallbacks for unsupported attributes.
+    ///
+    /// # Arguments
+    /// * `caps` - The `Capabilities` of the terminal.
+    ///
+    /// # Returns
+    /// A `String` containing the ANSI-formatted text.
+    pub fn format_for(&self, caps: &Capabilities) -> String {
+        let codes = self.style.sgr_params(caps);
+        let styled = if !codes.is_empty() {
+            format!("\x1B[{}m{}\x1B[0m", codes, self.text)
+        } else {
+            self.text.to_string()
+        };
+        match &self.link {
+            Some(link) if caps.hyperlinks && caps.colors != ColorLevel::None => {
+                format!("{}{}{}", link.open(), styled, LINK_END)
+            }
+            _ => styled,
+        }
+    }
+}
+
+impl From<String> for StyledText<'static> {
+    fn from(text: String) -> Self {
+        Self {
+            text: Cow::Owned(text),
+            style: Style::new(),
+            link: None,
+        }
+    }
+}
+
+/// Implements the `Display` trait for `StyledText`, allowing it to be printed directly.
+impl fmt::Display for StyledText<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.format_for(&capabilities()))
+    }
+}
+
+/// A string made out of multiple differently styled spans.
+///
+/// Adjacent spans sharing the same style and link are merged and empty spans are dropped, so two
+/// `StyledString`s that look the same on the terminal also compare equal.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StyledString {
+    spans: Vec<StyledText<'static>>,
+}
+
+impl StyledString {
+    /// Creates an empty `StyledString`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a styled span.
+    pub fn push(&mut self, span: StyledText<'_>) {
+        if span.text.is_empty() {
+            return;
+        }
+        match self.spans.last_mut() {
+            Some(last) if last.style == span.style && last.link == span.link => {
+                last.text.to_mut().push_str(&span.text)
+            }
+            _ => self.spans.push(span.into_owned()),
+        }
+    }
+
+    /// Appends `text` with the given `style`.
+    pub fn push_str(&mut self, text: &str, style: Style) {
+        self.push(StyledText::new(text).with_style(style));
+    }
+
+    /// Returns the spans this string is made of.
+    pub fn spans(&self) -> &[StyledText<'static>] {
+        &self.spans
+    }
+
+    /// Returns the text without any styling.
+    pub fn plain_text(&self) -> String {
+        self.spans.iter().map(|s| s.text()).collect()
+    }
+
+    /// Returns the number of terminal cells the string occupies, see [`display_width`].
+    pub fn width(&self) -> usize {
+        display_width(&self.plain_text())
+    }
+
+    /// Returns `true` if the string contains no text.
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Splits the string at line breaks, keeping the style of the spans on every line.
+    pub fn lines(&self) -> Vec<StyledString> {
+        let mut lines = vec![StyledString::new()];
+        for span in &self.spans {
+            for (i, part) in span.text.split('\n').enumerate() {
+                if i > 0 {
+                    lines.push(StyledString::new());
+                }
+                let mut part = StyledText::new(part).with_style(span.style);
+                part.link = span.link.clone();
+                lines.last_mut().unwrap().push(part);
+            }
+        }
+        lines
+    }
+
+    /// Converts the string into a formatted ANSI escape sequence string.
+    ///
+    /// Like [`StyledText::format_sequence`], this emits all attributes regardless of the
+    /// terminal, while `Display` applies the fallbacks for its [`capabilities`].
+    pub fn format_sequence(&self) -> String {
+        self.format_for(&Capabilities::all())
+    }
+
+    /// Converts the string into a formatted ANSI escape sequence string for a terminal with the
+    /// given capabilities, see [`StyledText::format_for`].
+    pub fn format_for(&self, caps: &Capabilities) -> String {
+        self.spans.iter().map(|s| s.format_for(caps)).collect()
+    }
+}
+
+impl From<&str> for StyledString {
+    fn from(text: &str) -> Self {
+        StyledText::new(text).into()
+    }
+}
+
+impl From<String> for StyledString {
+    fn from(text: String) -> Self {
+        StyledText::from(text).into()
+    }
+}
+
+impl<'a> From<StyledText<'a>> for StyledString {
+    fn from(span: StyledText<'a>) -> Self {
+        let mut string = StyledString::new();
+        string.push(span);
+        string
+    }
+}
+
+impl<'a> Extend<StyledText<'a>> for StyledString {
+    fn extend<I: IntoIterator<Item = StyledText<'a>>>(&mut self, iter: I) {
+        for span in iter {
+            self.push(span);
+        }
+    }
+}
+
+impl<'a> FromIterator<StyledText<'a>> for StyledString {
+    fn from_iter<I: IntoIterator<Item = StyledText<'a>>>(iter: I) -> Self {
+        let mut string = StyledString::new();
+        string.extend(iter);
+        string
+    }
+}
+
+impl fmt::Display for StyledString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.format_for(&capabilities()))
+    }
+}
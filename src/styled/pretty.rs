@@ -0,0 +1,550 @@
+//! Pretty-Printing
+//!
+//! Renders any value implementing [`Serialize`] as indented, syntax highlighted text, enabled
+//! with the `serde` feature. The format follows the serde data model like JSON does: structs and
+//! maps become `{ key: value }` tables, sequences and tuples `[a, b]` arrays, and `None` and unit
+//! values `null`. Keys that are valid identifiers are printed without quotes.
+
+use std::{error, fmt};
+
+use serde::ser::{self, Serialize};
+
+use super::{Style, StyledString};
+use crate::{control::terminal_size, theme::theme};
+
+/// Pretty-prints a serializable value with the colors of the current [theme](crate::theme).
+///
+/// Tables are printed with one entry per line, arrays are folded onto a single line as long as
+/// they fit into the [`width`](Pretty::width).
+///
+/// # Example
+///
+/// ```rust
+/// use console_utils::styled::pretty;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Config {
+///     name: &'static str,
+///     port: u16,
+///     verbose: bool,
+///     tags: Vec<&'static str>,
+///     proxy: Option<String>,
+/// }
+///
+/// let config = Config {
+///     name: "demo",
+///     port: 8080,
+///     verbose: false,
+///     tags: vec!["cli", "tui"],
+///     proxy: None,
+/// };
+/// let pretty = pretty(&config).unwrap();
+/// println!("{pretty}");
+/// assert_eq!(
+///     pretty.render().plain_text(),
+///     "{\n  \
+///        name: \"demo\",\n  \
+///        port: 8080,\n  \
+///        verbose: false,\n  \
+///        tags: [\"cli\", \"tui\"],\n  \
+///        proxy: null\n\
+///      }"
+/// );
+/// ```
+pub fn pretty<T: Serialize + ?Sized>(value: &T) -> Result<Pretty, PrettyError> {
+    Ok(Pretty {
+        value: value.serialize(ValueSerializer)?,
+        indent: 2,
+        width: None,
+    })
+}
+
+/// A serialized value ready to be pretty-printed, created by [`pretty()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pretty {
+    value: Value,
+    indent: usize,
+    width: Option<usize>,
+}
+
+impl Pretty {
+    /// Sets the number of spaces nested entries are indented by, 2 by default.
+    pub fn indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Sets the width arrays are folded to, instead of the width of the terminal or 80 columns
+    /// if it's unknown.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Renders the value into a styled string.
+    pub fn render(&self) -> StyledString {
+        let theme = theme();
+        let mut printer = Printer {
+            out: StyledString::new(),
+            styles: Styles {
+                key: theme.value_key_style,
+                string: theme.value_string_style,
+                number: theme.value_number_style,
+                bool: theme.value_bool_style,
+                null: theme.value_null_style,
+            },
+            indent: self.indent,
+            width: self
+                .width
+                .or_else(|| terminal_size().map(|(columns, _)| columns))
+                .unwrap_or(80),
+        };
+        printer.value(&self.value, 0, 0);
+        printer.out
+    }
+}
+
+impl fmt::Display for Pretty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+/// The error returned by [`pretty()`] if the value fails to serialize itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrettyError(String);
+
+impl fmt::Display for PrettyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl error::Error for PrettyError {}
+
+impl ser::Error for PrettyError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+/// A value of the serde data model.
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Null,
+    Bool(bool),
+    /// A number, already formatted.
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Returns the text of a map key, which may be any value.
+    fn into_key(self) -> String {
+        match self {
+            Value::String(key) => key,
+            value => {
+                let mut printer = Printer {
+                    out: StyledString::new(),
+                    styles: Styles::default(),
+                    indent: 0,
+                    width: usize::MAX,
+                };
+                printer.value(&value, 0, 0);
+                printer.out.plain_text()
+            }
+        }
+    }
+}
+
+/// The styles of the different kinds of values.
+#[derive(Default)]
+struct Styles {
+    key: Style,
+    string: Style,
+    number: Style,
+    bool: Style,
+    null: Style,
+}
+
+/// Writes values into a styled string.
+struct Printer {
+    out: StyledString,
+    styles: Styles,
+    indent: usize,
+    width: usize,
+}
+
+impl Printer {
+    /// Writes `value` at the given nesting `depth`, starting at column `col`.
+    fn value(&mut self, value: &Value, depth: usize, col: usize) {
+        if let Some(folded) = self.fold(value) {
+            let array = matches!(value, Value::Array(items) if !items.is_empty());
+            // leave room for a separating comma
+            if !array || col + folded.width() < self.width {
+                self.out.extend(folded.spans().iter().cloned());
+                return;
+            }
+        }
+        match value {
+            Value::Array(items) => {
+                self.out.push_str("[", Style::new());
+                for (i, item) in items.iter().enumerate() {
+                    let col = self.line_start(depth + 1);
+                    self.value(item, depth + 1, col);
+                    self.separator(i, items.len());
+                }
+                self.line_start(depth);
+                self.out.push_str("]", Style::new());
+            }
+            Value::Object(entries) => {
+                self.out.push_str("{", Style::new());
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    let col = self.line_start(depth + 1) + self.key(key);
+                    self.value(value, depth + 1, col);
+                    self.separator(i, entries.len());
+                }
+                self.line_start(depth);
+                self.out.push_str("}", Style::new());
+            }
+            _ => unreachable!("scalars and empty arrays and tables are always folded"),
+        }
+    }
+
+    /// Returns `value` on a single line, `None` for non-empty tables.
+    fn fold(&self, value: &Value) -> Option<StyledString> {
+        let mut out = StyledString::new();
+        match value {
+            Value::Null => out.push_str("null", self.styles.null),
+            Value::Bool(b) => out.push_str(&b.to_string(), self.styles.bool),
+            Value::Number(n) => out.push_str(n, self.styles.number),
+            Value::String(s) => out.push_str(&format!("{s:?}"), self.styles.string),
+            Value::Array(items) => {
+                out.push_str("[", Style::new());
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(", ", Style::new());
+                    }
+                    out.extend(self.fold(item)?.spans().iter().cloned());
+                }
+                out.push_str("]", Style::new());
+            }
+            Value::Object(entries) if entries.is_empty() => out.push_str("{}", Style::new()),
+            Value::Object(_) => return None,
+        }
+        Some(out)
+    }
+
+    /// Starts a new line indented for `depth` and returns its column.
+    fn line_start(&mut self, depth: usize) -> usize {
+        let col = depth * self.indent;
+        self.out.push_str("\n", Style::new());
+        self.out.push_str(&" ".repeat(col), Style::new());
+        col
+    }
+
+    /// Writes a key and its colon, quoted unless it's an identifier, and returns its width.
+    fn key(&mut self, key: &str) -> usize {
+        let identifier = key.starts_with(|c: char| c.is_alphabetic() || c == '_')
+            && key
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+        let key = if identifier {
+            key.to_string()
+        } else {
+            format!("{key:?}")
+        };
+        self.out.push_str(&key, self.styles.key);
+        self.out.push_str(": ", Style::new());
+        StyledString::from(key).width() + 2
+    }
+
+    /// Writes the comma after the `i`th of `len` entries.
+    fn separator(&mut self, i: usize, len: usize) {
+        if i + 1 < len {
+            self.out.push_str(",", Style::new());
+        }
+    }
+}
+
+/// Serializes values into [`Value`]s.
+struct ValueSerializer;
+
+macro_rules! serialize_numbers {
+    ($($method:ident: $ty:ty),*) => {
+        $(
+            fn $method(self, v: $ty) -> Result<Value, PrettyError> {
+                Ok(Value::Number(v.to_string()))
+            }
+        )*
+    };
+}
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = PrettyError;
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = VariantSerializer<SeqSerializer>;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = VariantSerializer<MapSerializer>;
+
+    serialize_numbers!(
+        serialize_i8: i8, serialize_i16: i16, serialize_i32: i32, serialize_i64: i64,
+        serialize_i128: i128, serialize_u8: u8, serialize_u16: u16, serialize_u32: u32,
+        serialize_u64: u64, serialize_u128: u128, serialize_f32: f32, serialize_f64: f64
+    );
+
+    fn serialize_bool(self, v: bool) -> Result<Value, PrettyError> {
+        Ok(Value::Bool(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value, PrettyError> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value, PrettyError> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, PrettyError> {
+        Ok(Value::Array(
+            v.iter().map(|b| Value::Number(b.to_string())).collect(),
+        ))
+    }
+
+    fn serialize_none(self) -> Result<Value, PrettyError> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value, PrettyError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value, PrettyError> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, PrettyError> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Value, PrettyError> {
+        Ok(Value::String(variant.to_string()))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Value, PrettyError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value, PrettyError> {
+        Ok(Value::Object(vec![(
+            variant.to_string(),
+            value.serialize(self)?,
+        )]))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer, PrettyError> {
+        Ok(SeqSerializer(Vec::with_capacity(len.unwrap_or(0))))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqSerializer, PrettyError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer, PrettyError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<VariantSerializer<SeqSerializer>, PrettyError> {
+        Ok(VariantSerializer {
+            variant,
+            inner: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<MapSerializer, PrettyError> {
+        Ok(MapSerializer {
+            entries: Vec::with_capacity(len.unwrap_or(0)),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<MapSerializer, PrettyError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<VariantSerializer<MapSerializer>, PrettyError> {
+        Ok(VariantSerializer {
+            variant,
+            inner: self.serialize_map(Some(len))?,
+        })
+    }
+}
+
+/// Collects the elements of sequences and tuples.
+struct SeqSerializer(Vec<Value>);
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = Value;
+    type Error = PrettyError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), PrettyError> {
+        self.0.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, PrettyError> {
+        Ok(Value::Array(self.0))
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer {
+    type Ok = Value;
+    type Error = PrettyError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), PrettyError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value, PrettyError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer {
+    type Ok = Value;
+    type Error = PrettyError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), PrettyError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value, PrettyError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+/// Collects the entries of maps and structs.
+struct MapSerializer {
+    entries: Vec<(String, Value)>,
+    /// The key of the entry whose value is serialized next.
+    key: Option<String>,
+}
+
+impl ser::SerializeMap for MapSerializer {
+    type Ok = Value;
+    type Error = PrettyError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), PrettyError> {
+        self.key = Some(key.serialize(ValueSerializer)?.into_key());
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), PrettyError> {
+        let key = self.key.take().unwrap_or_default();
+        self.entries.push((key, value.serialize(ValueSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, PrettyError> {
+        Ok(Value::Object(self.entries))
+    }
+}
+
+impl ser::SerializeStruct for MapSerializer {
+    type Ok = Value;
+    type Error = PrettyError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), PrettyError> {
+        self.entries
+            .push((key.to_string(), value.serialize(ValueSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, PrettyError> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+/// Wraps the fields of an enum variant into a table with the variant name as its only key.
+struct VariantSerializer<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl ser::SerializeTupleVariant for VariantSerializer<SeqSerializer> {
+    type Ok = Value;
+    type Error = PrettyError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), PrettyError> {
+        ser::SerializeSeq::serialize_element(&mut self.inner, value)
+    }
+
+    fn end(self) -> Result<Value, PrettyError> {
+        let fields = ser::SerializeSeq::end(self.inner)?;
+        Ok(Value::Object(vec![(self.variant.to_string(), fields)]))
+    }
+}
+
+impl ser::SerializeStructVariant for VariantSerializer<MapSerializer> {
+    type Ok = Value;
+    type Error = PrettyError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), PrettyError> {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> Result<Value, PrettyError> {
+        let fields = ser::SerializeMap::end(self.inner)?;
+        Ok(Value::Object(vec![(self.variant.to_string(), fields)]))
+    }
+}
@@ -0,0 +1,258 @@
+//! Serde Support
+//!
+//! Serialization of colors, styles, styled text and palettes, enabled with the `serde` feature.
+//! The formats are meant to be written by hand, e.g. in a configuration file:
+//!
+//! - [`Color`]s are strings accepted by its [`FromStr`](std::str::FromStr) implementation:
+//!   names like `bright-black`, hex colors like `#ff8800` and palette indices like `ansi(208)`.
+//! - [`Style`]s are tables of their attributes, like `{ fg = "red", bold = true }`. The
+//!   underline is given by its line style, like `underline = "curly"`.
+//! - [`StyledText`] is either a plain string or a table with `text`, `style` and `link`.
+//! - [`Palette`]s are tables from color names, `foreground` and `background` to colors.
+//!
+//! Missing attributes and palette entries keep their default, unknown ones are rejected.
+
+use std::{collections::BTreeMap, fmt};
+
+use serde::{
+    de::{self, value::MapAccessDeserializer, MapAccess, Visitor},
+    ser::{self, SerializeMap},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use super::{Color, Link, Palette, Style, StyledText, Underline, NAMED_COLORS};
+
+impl Color {
+    /// Returns the configuration string of the color, `None` for raw ANSI codes that aren't
+    /// one of the named colors.
+    fn to_config(self) -> Option<String> {
+        let named = |color: Color| {
+            NAMED_COLORS
+                .iter()
+                .find(|(_, c)| c.code() == color.code())
+                .map(|(name, _)| name.to_string())
+        };
+        match self {
+            Color::Rgb(r, g, b) => Some(format!("#{r:02x}{g:02x}{b:02x}")),
+            Color::Indexed(i) => Some(format!("ansi({i})")),
+            color => named(color),
+        }
+    }
+}
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.to_config() {
+            Some(config) => serializer.serialize_str(&config),
+            None => Err(ser::Error::custom(format_args!(
+                "the raw ANSI code {:?} has no color name",
+                self
+            ))),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+/// The serialized form of a [`Style`].
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename = "Style", default, deny_unknown_fields)]
+struct StyleDef {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fg: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bg: Option<Color>,
+    #[serde(skip_serializing_if = "is_false")]
+    bold: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    dim: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    italic: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    underline: Option<Underline>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    underline_color: Option<Color>,
+    #[serde(skip_serializing_if = "is_false")]
+    blink: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    reverse: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    hidden: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    strikethrough: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    overline: bool,
+}
+
+fn is_false(b: &bool) -> bool {
+    !b
+}
+
+impl Serialize for Style {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        StyleDef {
+            fg: self.fg,
+            bg: self.bg,
+            bold: self.bold,
+            dim: self.dim,
+            italic: self.italic,
+            underline: self.underline,
+            underline_color: self.underline_color,
+            blink: self.blink,
+            reverse: self.reverse,
+            hidden: self.hidden,
+            strikethrough: self.strikethrough,
+            overline: self.overline,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Style {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let def = StyleDef::deserialize(deserializer)?;
+        Ok(Style {
+            fg: def.fg,
+            bg: def.bg,
+            bold: def.bold,
+            dim: def.dim,
+            italic: def.italic,
+            underline: def.underline,
+            underline_color: def.underline_color,
+            blink: def.blink,
+            reverse: def.reverse,
+            hidden: def.hidden,
+            strikethrough: def.strikethrough,
+            overline: def.overline,
+        })
+    }
+}
+
+/// The serialized form of a [`Link`].
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Link", deny_unknown_fields)]
+struct LinkDef {
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+}
+
+impl Serialize for Link {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        LinkDef {
+            url: self.url.clone(),
+            id: self.id.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Link {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let def = LinkDef::deserialize(deserializer)?;
+        let link = Link::new(&def.url);
+        Ok(match def.id {
+            Some(id) => link.with_id(&id),
+            None => link,
+        })
+    }
+}
+
+/// The serialized form of a [`StyledText`] with a style or link.
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "StyledText", deny_unknown_fields)]
+struct StyledTextDef {
+    text: String,
+    #[serde(default, skip_serializing_if = "Style::is_plain")]
+    style: Style,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    link: Option<Link>,
+}
+
+impl Serialize for StyledText<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.style.is_plain() && self.link.is_none() {
+            return serializer.serialize_str(&self.text);
+        }
+        StyledTextDef {
+            text: self.text.to_string(),
+            style: self.style,
+            link: self.link.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StyledText<'static> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TextVisitor;
+
+        impl<'de> Visitor<'de> for TextVisitor {
+            type Value = StyledText<'static>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a string or a table with `text`, `style` and `link`")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(StyledText::from(v.to_string()))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+                let def = StyledTextDef::deserialize(MapAccessDeserializer::new(map))?;
+                Ok(StyledText {
+                    text: def.text.into(),
+                    style: def.style,
+                    link: def.link,
+                })
+            }
+        }
+
+        deserializer.deserialize_any(TextVisitor)
+    }
+}
+
+impl Serialize for Palette {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let hex = |(r, g, b): (u8, u8, u8)| Color::Rgb(r, g, b);
+        let mut map = serializer.serialize_map(Some(NAMED_COLORS.len() + 2))?;
+        map.serialize_entry("foreground", &hex(self.foreground))?;
+        map.serialize_entry("background", &hex(self.background))?;
+        for ((_, color), rgb) in NAMED_COLORS.iter().zip(self.colors) {
+            map.serialize_entry(&color.to_config().unwrap_or_default(), &hex(rgb))?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Palette {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let entries = BTreeMap::<String, Color>::deserialize(deserializer)?;
+        let defaults = Palette::default();
+        let mut palette = Palette::default();
+        for (name, color) in entries {
+            let rgb = defaults.rgb(color);
+            match name.as_str() {
+                "foreground" => palette = palette.foreground(rgb),
+                "background" => palette = palette.background(rgb),
+                _ => match name.parse::<Color>() {
+                    Ok(named) if NAMED_COLORS.iter().any(|(_, c)| *c == named) => {
+                        palette = palette.color(named, rgb)
+                    }
+                    _ => {
+                        return Err(de::Error::custom(format_args!(
+                            "unknown palette entry `{name}`, expected a color name like `red` or \
+                             `bright-black`, `foreground` or `background`"
+                        )))
+                    }
+                },
+            }
+        }
+        Ok(palette)
+    }
+}
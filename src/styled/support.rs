@@ -0,0 +1,195 @@
+//! Terminal Capabilities
+//!
+//! Detects which escape sequences the terminal understands, so attributes without universal
+//! support can fall back to ones that are, and colors can be downgraded to the ones the terminal
+//! is able to display.
+
+use std::{env, sync::RwLock};
+
+use super::{Color, Palette, NAMED_COLORS};
+use crate::control::enable_ansi_support;
+
+/// Detected or overridden capabilities, `None` until first requested.
+static CAPABILITIES: RwLock<Option<Capabilities>> = RwLock::new(None);
+
+/// The colors a terminal is able to display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorLevel {
+    /// No colors at all, e.g. because `NO_COLOR` is set. Formatting attributes are still used.
+    None,
+    /// The 16 named colors.
+    Ansi16,
+    /// The 256-color palette, see [`Color::Indexed`].
+    Ansi256,
+    /// Any 24-bit color, see [`Color::Rgb`].
+    TrueColor,
+}
+
+/// Describes which escape sequences a terminal understands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Capabilities {
+    /// The colors the terminal can display. Colors beyond it are replaced by the closest
+    /// supported one.
+    pub colors: ColorLevel,
+    /// Whether double, curly and dotted underlines as well as underline colors are supported.
+    ///
+    /// Without support, underlines fall back to a single line in the text color.
+    pub extended_underline: bool,
+    /// Whether OSC 8 hyperlinks are supported.
+    ///
+    /// Without support, linked text is printed without its link.
+    pub hyperlinks: bool,
+}
+
+impl Capabilities {
+    /// Capabilities of a terminal supporting everything this crate can emit.
+    pub const fn all() -> Self {
+        Self {
+            colors: ColorLevel::TrueColor,
+            extended_underline: true,
+            hyperlinks: true,
+        }
+    }
+
+    /// Capabilities of a terminal only supporting the 16 named colors and the basic attributes.
+    pub const fn basic() -> Self {
+        Self {
+            colors: ColorLevel::Ansi16,
+            extended_underline: false,
+            hyperlinks: false,
+        }
+    }
+
+    /// Detects the capabilities of the terminal from environment variables like `NO_COLOR`,
+    /// `COLORTERM`, `TERM`, `TERM_PROGRAM` and `VTE_VERSION`.
+    ///
+    /// Unknown terminals are assumed to only support the basic capabilities, except for
+    /// hyperlinks, which are only disabled for terminals known to print them as garbage. Colors
+    /// and hyperlinks are disabled for Windows consoles without [ANSI support].
+    ///
+    /// [ANSI support]: enable_ansi_support
+    pub fn detect() -> Self {
+        let var = |name| env::var(name).ok();
+        let term = var("TERM").unwrap_or_default();
+        let program = var("TERM_PROGRAM").unwrap_or_default();
+        let vte = var("VTE_VERSION")
+            .and_then(|v| v.parse::<u32>().ok())
+            .unwrap_or(0);
+
+        let ansi = enable_ansi_support();
+
+        let colors = if !ansi || var("NO_COLOR").map_or(false, |v| !v.is_empty()) || term == "dumb"
+        {
+            ColorLevel::None
+        } else if matches!(var("COLORTERM").as_deref(), Some("truecolor" | "24bit"))
+            || ["WezTerm", "iTerm.app", "ghostty", "vscode"].contains(&program.as_str())
+            || var("WT_SESSION").is_some()
+            || term.contains("kitty")
+            || term.contains("direct")
+        {
+            ColorLevel::TrueColor
+        } else if term.contains("256color") {
+            ColorLevel::Ansi256
+        } else {
+            ColorLevel::Ansi16
+        };
+
+        let extended_underline = ["kitty", "wezterm", "foot", "ghostty", "contour"]
+            .iter()
+            .any(|t| term.contains(t))
+            || ["WezTerm", "iTerm.app", "ghostty", "vscode"].contains(&program.as_str())
+            || var("KITTY_WINDOW_ID").is_some()
+            || vte >= 5102;
+
+        let hyperlinks = ansi
+            && !["dumb", "linux", "cygwin"].contains(&term.as_str())
+            && !term.starts_with("screen")
+            && program != "Apple_Terminal"
+            && (vte == 0 || vte >= 5000);
+
+        Self {
+            colors,
+            extended_underline,
+            hyperlinks,
+        }
+    }
+}
+
+/// Returns the capabilities of the terminal used when printing styled text.
+///
+/// They are detected once with [`Capabilities::detect`], unless overridden with
+/// [`set_capabilities`]. This also enables [ANSI support] on Windows.
+///
+/// [ANSI support]: enable_ansi_support
+pub fn capabilities() -> Capabilities {
+    enable_ansi_support();
+    if let Some(caps) = *CAPABILITIES.read().unwrap_or_else(|e| e.into_inner()) {
+        return caps;
+    }
+    let caps = Capabilities::detect();
+    *CAPABILITIES.write().unwrap_or_else(|e| e.into_inner()) = Some(caps);
+    caps
+}
+
+/// Overrides the detected capabilities of the terminal, e.g. based on a command line flag.
+pub fn set_capabilities(caps: Capabilities) {
+    *CAPABILITIES.write().unwrap_or_else(|e| e.into_inner()) = Some(caps);
+}
+
+impl Color {
+    /// Returns the closest color displayable at `level`, or `None` if colors are disabled.
+    pub fn downgrade(self, level: ColorLevel) -> Option<Color> {
+        match (self, level) {
+            (_, ColorLevel::None) => None,
+            (Color::Rgb(r, g, b), ColorLevel::Ansi256) => Some(Color::Indexed(to_256(r, g, b))),
+            (Color::Rgb(..), ColorLevel::Ansi16)
+            | (Color::Indexed(16..=255), ColorLevel::Ansi16) => {
+                Some(to_16(Palette::default().rgb(self)))
+            }
+            (Color::Indexed(i), ColorLevel::Ansi16) => Some(NAMED_COLORS[i as usize].1),
+            (color, _) => Some(color),
+        }
+    }
+}
+
+/// Squared distance between two RGB colors.
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+/// Returns the closest entry of the 6x6x6 color cube or the grayscale ramp.
+fn to_256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |v: u8| match v {
+        0..=47 => 0,
+        48..=114 => 1,
+        v => (v - 35) / 40,
+    };
+    let value = |l: u8| if l == 0 { 0 } else { 55 + l * 40 };
+    let (lr, lg, lb) = (level(r), level(g), level(b));
+    let cube = (value(lr), value(lg), value(lb));
+
+    let average = (r as u16 + g as u16 + b as u16) / 3;
+    let gray_index = if average < 8 {
+        0
+    } else {
+        ((average - 8) / 10).min(23) as u8
+    };
+    let gray = 8 + gray_index * 10;
+
+    if distance((r, g, b), (gray, gray, gray)) < distance((r, g, b), cube) {
+        232 + gray_index
+    } else {
+        16 + 36 * lr + 6 * lg + lb
+    }
+}
+
+/// Returns the named color closest to `rgb` in the default [`Palette`].
+fn to_16(rgb: (u8, u8, u8)) -> Color {
+    let palette = Palette::default();
+    NAMED_COLORS
+        .iter()
+        .map(|(_, color)| *color)
+        .min_by_key(|c| distance(rgb, palette.rgb(*c)))
+        .unwrap_or(Color::White)
+}
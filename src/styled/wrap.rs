@@ -0,0 +1,281 @@
+//! Word Wrapping
+//!
+//! Wraps text containing ANSI escape sequences to a maximum width in terminal cells, breaking
+//! lines between words. Styles and hyperlinks active at an indented line break are ended before
+//! it and continued after the indentation of the next line, so the indentation is never styled.
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::{
+    ansi::{grapheme_width, parse_link},
+    display_width, segments, Segment, LINK_END,
+};
+
+/// Wraps text to a maximum width, with optional indentation.
+///
+/// Lines are broken at whitespace, which is removed at the break. Words longer than a whole
+/// line are broken between their characters. Characters are never split, so one wider than a
+/// whole line, like an East Asian wide character at width 1, gets a line of its own and sticks
+/// out of it. Existing line breaks are kept.
+///
+/// # Example
+///
+/// ```rust
+/// use console_utils::styled::Wrap;
+///
+/// let text = Wrap::new(16)
+///     .indent("  ")
+///     .hanging_indent("- ")
+///     .wrap("The quick brown fox jumps over the lazy dog");
+/// assert_eq!(text, "  The quick\n  - brown fox\n  - jumps over\n  - the lazy dog");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Wrap {
+    width: usize,
+    indent: String,
+    hanging_indent: String,
+}
+
+impl Wrap {
+    /// Creates a wrapper for lines of at most `width` terminal cells, including indentation.
+    pub fn new(width: usize) -> Self {
+        Self {
+            width,
+            indent: String::new(),
+            hanging_indent: String::new(),
+        }
+    }
+
+    /// Sets the indentation of every line.
+    pub fn indent(mut self, indent: &str) -> Self {
+        self.indent = indent.into();
+        self
+    }
+
+    /// Sets additional indentation for lines continuing a wrapped line, printed after the
+    /// [`indent`](Wrap::indent).
+    pub fn hanging_indent(mut self, indent: &str) -> Self {
+        self.hanging_indent = indent.into();
+        self
+    }
+
+    /// Wraps `text`.
+    pub fn wrap(&self, text: &str) -> String {
+        let continuation = format!("{}{}", self.indent, self.hanging_indent);
+        // always leave room for at least one character
+        let limit = self
+            .width
+            .max(display_width(&self.indent) + 1)
+            .max(display_width(&continuation) + 1);
+
+        let mut out = Writer {
+            out: self.indent.clone(),
+            col: display_width(&self.indent),
+            start: display_width(&self.indent),
+            paragraph_start: true,
+            ..Writer::default()
+        };
+        let mut space: Option<Token> = None;
+        for token in tokens(text) {
+            match token.kind {
+                Kind::Newline(newline) => {
+                    out.escapes(space.take());
+                    out.new_line(newline, &self.indent, true);
+                }
+                Kind::Space => space = Some(token),
+                Kind::Word => {
+                    let space_width = space.as_ref().map_or(0, |s| s.width);
+                    if !out.has_content() && !out.paragraph_start {
+                        out.escapes(space.take());
+                    } else if out.has_content() && out.col + space_width + token.width > limit {
+                        out.escapes(space.take());
+                        out.new_line("\n", &continuation, false);
+                    }
+                    out.pieces(space.take());
+                    for piece in token.pieces {
+                        if let Piece::Grapheme(_, width) = piece {
+                            if out.has_content() && out.col + width > limit {
+                                out.new_line("\n", &continuation, false);
+                            }
+                        }
+                        out.piece(piece);
+                    }
+                }
+            }
+        }
+        match space {
+            Some(space) if out.col + space.width <= limit => out.pieces(Some(space)),
+            space => out.escapes(space),
+        }
+        out.out
+    }
+}
+
+/// Wraps `text` to lines of at most `width` terminal cells, see [`Wrap`].
+///
+/// # Example
+///
+/// ```rust
+/// use console_utils::styled::wrap;
+///
+/// assert_eq!(wrap("Hello beautiful world", 10), "Hello\nbeautiful\nworld");
+/// ```
+pub fn wrap(text: &str, width: usize) -> String {
+    Wrap::new(width).wrap(text)
+}
+
+/// A grapheme with its width, or an escape sequence.
+#[derive(Clone, Copy)]
+enum Piece<'a> {
+    Grapheme(&'a str, usize),
+    Escape(&'a str),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind<'a> {
+    /// A line break, `\n` or `\r\n`.
+    Newline(&'a str),
+    /// Whitespace between words.
+    Space,
+    /// A word, or escape sequences before any text.
+    Word,
+}
+
+/// A run of pieces of the same kind, escape sequences are part of the run they appear in.
+struct Token<'a> {
+    kind: Kind<'a>,
+    pieces: Vec<Piece<'a>>,
+    width: usize,
+}
+
+/// Splits `text` into words, whitespace and line breaks.
+fn tokens(text: &str) -> Vec<Token<'_>> {
+    let mut tokens: Vec<Token> = Vec::new();
+    for segment in segments(text) {
+        let graphemes = match segment {
+            Segment::Escape(escape) => {
+                match tokens.last_mut() {
+                    Some(last) if !matches!(last.kind, Kind::Newline(_)) => {
+                        last.pieces.push(Piece::Escape(escape))
+                    }
+                    _ => tokens.push(Token {
+                        kind: Kind::Word,
+                        pieces: vec![Piece::Escape(escape)],
+                        width: 0,
+                    }),
+                }
+                continue;
+            }
+            Segment::Text(text) => text.graphemes(true),
+        };
+        for grapheme in graphemes {
+            let kind = if grapheme == "\n" || grapheme == "\r\n" {
+                Kind::Newline(grapheme)
+            } else if grapheme.chars().all(char::is_whitespace) {
+                Kind::Space
+            } else {
+                Kind::Word
+            };
+            let width = grapheme_width(grapheme);
+            match tokens.last_mut() {
+                Some(last) if last.kind == kind && !matches!(kind, Kind::Newline(_)) => {
+                    last.pieces.push(Piece::Grapheme(grapheme, width));
+                    last.width += width;
+                }
+                _ => tokens.push(Token {
+                    kind,
+                    pieces: vec![Piece::Grapheme(grapheme, width)],
+                    width,
+                }),
+            }
+        }
+    }
+    tokens
+}
+
+/// The wrapped output, keeping track of the active styles and link.
+#[derive(Default)]
+struct Writer {
+    out: String,
+    /// The current column.
+    col: usize,
+    /// The column after the indentation of the current line.
+    start: usize,
+    /// Whether the current line starts a paragraph, rather than continuing a wrapped one.
+    paragraph_start: bool,
+    /// The SGR sequences applied since the last reset.
+    sgr: String,
+    /// The OSC 8 sequence of the open hyperlink.
+    link: Option<String>,
+}
+
+impl Writer {
+    fn has_content(&self) -> bool {
+        self.col > self.start
+    }
+
+    /// Ends the current line with `newline`, then indents the next one.
+    ///
+    /// Active styles and links are interrupted for the indentation.
+    fn new_line(&mut self, newline: &str, indent: &str, paragraph_start: bool) {
+        let interrupt = !indent.is_empty();
+        if interrupt && self.link.is_some() {
+            self.out.push_str(LINK_END);
+        }
+        if interrupt && !self.sgr.is_empty() {
+            self.out.push_str("\x1b[0m");
+        }
+        self.out.push_str(newline);
+        self.out.push_str(indent);
+        self.col = display_width(indent);
+        self.start = self.col;
+        self.paragraph_start = paragraph_start;
+        if interrupt {
+            self.out.push_str(&self.sgr);
+            if let Some(link) = &self.link {
+                self.out.push_str(link);
+            }
+        }
+    }
+
+    fn piece(&mut self, piece: Piece) {
+        match piece {
+            Piece::Grapheme(grapheme, width) => {
+                self.out.push_str(grapheme);
+                self.col += width;
+            }
+            Piece::Escape(escape) => {
+                self.out.push_str(escape);
+                if let Some(params) = escape
+                    .strip_prefix("\x1b[")
+                    .and_then(|e| e.strip_suffix('m'))
+                {
+                    if matches!(params.split(';').next(), Some("" | "0")) {
+                        self.sgr.clear();
+                    }
+                    if !matches!(params, "" | "0") {
+                        self.sgr.push_str(escape);
+                    }
+                } else if let Some(osc) = escape.strip_prefix("\x1b]8;") {
+                    self.link = parse_link(osc).map(|_| escape.to_string());
+                }
+            }
+        }
+    }
+
+    /// Writes all pieces of `token`.
+    fn pieces(&mut self, token: Option<Token>) {
+        for piece in token.into_iter().flat_map(|t| t.pieces) {
+            self.piece(piece);
+        }
+    }
+
+    /// Writes only the escape sequences of `token`, dropping its text.
+    fn escapes(&mut self, token: Option<Token>) {
+        for piece in token.into_iter().flat_map(|t| t.pieces) {
+            if let Piece::Escape(_) = piece {
+                self.piece(piece);
+            }
+        }
+    }
+}
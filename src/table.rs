@@ -0,0 +1,423 @@
+//! Table Utilities
+//!
+//! This module provides a [`Table`] for printing rows of styled cells in aligned columns, with
+//! optional headers, borders and striped rows. Widths are measured in terminal cells, so styled
+//! text, East Asian wide characters and emoji line up correctly.
+
+use std::fmt;
+
+use crate::{
+    control::terminal_size,
+    styled::{parse_ansi, truncate_to_width, wrap, Style, StyledString},
+};
+
+/// The horizontal alignment of the cells of a column.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Align {
+    /// Aligned to the left edge of the column.
+    #[default]
+    Left,
+    /// Centered, with the extra space on the right if it can't be split evenly.
+    Center,
+    /// Aligned to the right edge of the column, e.g. for numbers.
+    Right,
+}
+
+impl Align {
+    /// Splits `padding` into the space before and after aligned content.
+    pub(crate) fn split(self, padding: usize) -> (usize, usize) {
+        match self {
+            Align::Left => (0, padding),
+            Align::Center => (padding / 2, padding - padding / 2),
+            Align::Right => (padding, 0),
+        }
+    }
+}
+
+/// How cells too wide for their column are shortened.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Overflow {
+    /// Cut off at the column width and ended with `…`.
+    #[default]
+    Truncate,
+    /// Wrapped between words onto additional lines. Characters wider than the whole column are
+    /// replaced with `…`.
+    Wrap,
+}
+
+/// The characters the borders of tables and [panels](crate::panel) are drawn with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Border {
+    /// `+`, `-` and `|`, for terminals without box-drawing characters.
+    Ascii,
+    /// Thin lines with rounded corners.
+    #[default]
+    Rounded,
+    /// Thick lines.
+    Heavy,
+    /// No border at all. Table columns are separated by two spaces instead.
+    None,
+}
+
+/// The pieces of a border: left edge, horizontal line, junction and right edge of the top,
+/// header separator and bottom lines, and the vertical line.
+pub(crate) struct Chars {
+    pub(crate) top: [&'static str; 4],
+    pub(crate) middle: [&'static str; 4],
+    pub(crate) bottom: [&'static str; 4],
+    pub(crate) vertical: &'static str,
+}
+
+impl Border {
+    /// Returns the characters of the border, `None` for [`Border::None`].
+    pub(crate) fn chars(self) -> Option<Chars> {
+        match self {
+            Border::Ascii => Some(Chars {
+                top: ["+", "-", "+", "+"],
+                middle: ["+", "-", "+", "+"],
+                bottom: ["+", "-", "+", "+"],
+                vertical: "|",
+            }),
+            Border::Rounded => Some(Chars {
+                top: ["╭", "─", "┬", "╮"],
+                middle: ["├", "─", "┼", "┤"],
+                bottom: ["╰", "─", "┴", "╯"],
+                vertical: "│",
+            }),
+            Border::Heavy => Some(Chars {
+                top: ["┏", "━", "┳", "┓"],
+                middle: ["┣", "━", "╋", "┫"],
+                bottom: ["┗", "━", "┻", "┛"],
+                vertical: "┃",
+            }),
+            Border::None => None,
+        }
+    }
+}
+
+/// Renders rows of styled cells as a table with aligned columns.
+///
+/// Columns are as wide as their widest cell. If the table doesn't fit into the
+/// [`max_width`](Table::max_width), by default the width of the terminal or 80 columns if it's
+/// unknown, the widest columns are
+/// shrunk and their cells truncated or wrapped, see [`Overflow`].
+///
+/// # Example
+///
+/// ```rust
+/// use console_utils::{
+///     styled::{Color, Style, StyledText},
+///     table::{Align, Border, Table},
+/// };
+///
+/// let table = Table::new()
+///     .header(["Crate", "Downloads"])
+///     .row([StyledText::new("console-utils").fg(Color::Cyan), StyledText::new("1,024")])
+///     .row(["serde", "500,000,000"])
+///     .align(1, Align::Right)
+///     .border(Border::Ascii)
+///     .stripes(Style::new().bg(Color::BrightBlack));
+/// println!("{table}");
+/// assert_eq!(
+///     table.render().plain_text(),
+///     "+---------------+-------------+\n\
+///      | Crate         |   Downloads |\n\
+///      +---------------+-------------+\n\
+///      | console-utils |       1,024 |\n\
+///      | serde         | 500,000,000 |\n\
+///      +---------------+-------------+"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table {
+    header: Option<Vec<StyledString>>,
+    rows: Vec<Vec<StyledString>>,
+    align: Vec<Align>,
+    border: Border,
+    overflow: Overflow,
+    max_width: Option<usize>,
+    header_style: Style,
+    border_style: Style,
+    stripes: Option<Style>,
+}
+
+impl Default for Table {
+    fn default() -> Self {
+        Self {
+            header: None,
+            rows: Vec::new(),
+            align: Vec::new(),
+            border: Border::default(),
+            overflow: Overflow::default(),
+            max_width: None,
+            header_style: Style::new().bold(),
+            border_style: Style::new(),
+            stripes: None,
+        }
+    }
+}
+
+impl Table {
+    /// Creates an empty table with rounded borders and a bold header.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the header row.
+    pub fn header<I>(mut self, cells: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<StyledString>,
+    {
+        self.header = Some(cells.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Appends a row. Rows may have different lengths, missing cells are left empty.
+    pub fn row<I>(mut self, cells: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<StyledString>,
+    {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Appends a row to an existing table, e.g. inside a loop.
+    pub fn push_row<I>(&mut self, cells: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<StyledString>,
+    {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+    }
+
+    /// Sets the alignment of the column at `index`, counted from zero.
+    pub fn align(mut self, index: usize, align: Align) -> Self {
+        if self.align.len() <= index {
+            self.align.resize(index + 1, Align::default());
+        }
+        self.align[index] = align;
+        self
+    }
+
+    /// Sets the border style.
+    pub fn border(mut self, border: Border) -> Self {
+        self.border = border;
+        self
+    }
+
+    /// Sets how cells too wide for their column are shortened.
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Sets the maximum width of the whole table in terminal cells, instead of the width of the
+    /// terminal or 80 columns if it's unknown.
+    pub fn max_width(mut self, width: usize) -> Self {
+        self.max_width = Some(width);
+        self
+    }
+
+    /// Sets the style of the header cells, bold by default.
+    pub fn header_style(mut self, style: Style) -> Self {
+        self.header_style = style;
+        self
+    }
+
+    /// Sets the style of the border.
+    pub fn border_style(mut self, style: Style) -> Self {
+        self.border_style = style;
+        self
+    }
+
+    /// Applies `style` to every other row, starting with the second one, to make wide tables
+    /// easier to read. Usually a background color, like `Style::new().bg(Color::BrightBlack)`.
+    pub fn stripes(mut self, style: Style) -> Self {
+        self.stripes = Some(style);
+        self
+    }
+
+    /// Renders the table into a styled string.
+    pub fn render(&self) -> StyledString {
+        let columns = self
+            .header
+            .iter()
+            .chain(&self.rows)
+            .map(Vec::len)
+            .max()
+            .unwrap_or(0);
+        let mut out = StyledString::new();
+        if columns == 0 {
+            return out;
+        }
+        let widths = self.widths(columns);
+        let chars = self.border.chars();
+
+        if let Some(chars) = &chars {
+            self.write_rule(&mut out, &widths, chars.top);
+        }
+        if let Some(header) = &self.header {
+            self.write_row(&mut out, header, &widths, self.header_style, Style::new());
+            if let Some(chars) = &chars {
+                self.write_rule(&mut out, &widths, chars.middle);
+            }
+        }
+        for (i, row) in self.rows.iter().enumerate() {
+            let style = match self.stripes {
+                Some(style) if i % 2 == 1 => style,
+                _ => Style::new(),
+            };
+            self.write_row(&mut out, row, &widths, style, style);
+        }
+        if let Some(chars) = &chars {
+            self.write_rule(&mut out, &widths, chars.bottom);
+        }
+        out
+    }
+
+    /// Computes the width of every column, shrinking the widest ones to fit the maximum width.
+    fn widths(&self, columns: usize) -> Vec<usize> {
+        let mut widths = vec![0; columns];
+        for row in self.header.iter().chain(&self.rows) {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                let cell_width = cell.lines().iter().map(StyledString::width).max();
+                *width = (*width).max(cell_width.unwrap_or(0));
+            }
+        }
+
+        let overhead = match self.border {
+            Border::None => 2 * (columns - 1),
+            _ => 3 * columns + 1,
+        };
+        let available = max_width(self.max_width)
+            .saturating_sub(overhead)
+            .max(columns);
+        let mut total: usize = widths.iter().sum();
+        while total > available {
+            // shrink the widest column, so short columns stay intact as long as possible
+            let widest = widths.iter_mut().max().unwrap();
+            *widest -= 1;
+            total -= 1;
+        }
+        widths
+    }
+
+    /// Appends a horizontal border line.
+    fn write_rule(
+        &self,
+        out: &mut StyledString,
+        widths: &[usize],
+        [left, line, cross, right]: [&str; 4],
+    ) {
+        if !out.is_empty() {
+            out.push_str("\n", Style::new());
+        }
+        let mut rule = String::from(left);
+        for (i, width) in widths.iter().enumerate() {
+            if i > 0 {
+                rule.push_str(cross);
+            }
+            rule.push_str(&line.repeat(width + 2));
+        }
+        rule.push_str(right);
+        out.push_str(&rule, self.border_style);
+    }
+
+    /// Appends a row, `style` is layered below the styles of the cells, `fill` used for the
+    /// padding around them.
+    fn write_row(
+        &self,
+        out: &mut StyledString,
+        cells: &[StyledString],
+        widths: &[usize],
+        style: Style,
+        fill: Style,
+    ) {
+        let empty = StyledString::new();
+        let lines: Vec<Vec<StyledString>> = widths
+            .iter()
+            .enumerate()
+            .map(|(i, width)| self.cell_lines(cells.get(i).unwrap_or(&empty), style, *width))
+            .collect();
+        let height = lines.iter().map(Vec::len).max().unwrap_or(1);
+        let chars = self.border.chars();
+
+        for line in 0..height {
+            if !out.is_empty() {
+                out.push_str("\n", Style::new());
+            }
+            if let Some(chars) = &chars {
+                out.push_str(chars.vertical, self.border_style);
+                out.push_str(" ", fill);
+            }
+            for (i, width) in widths.iter().enumerate() {
+                if i > 0 {
+                    match &chars {
+                        Some(chars) => {
+                            out.push_str(" ", fill);
+                            out.push_str(chars.vertical, self.border_style);
+                            out.push_str(" ", fill);
+                        }
+                        None => out.push_str("  ", fill),
+                    }
+                }
+                let content = lines[i].get(line).cloned().unwrap_or_default();
+                let padding = width.saturating_sub(content.width());
+                let align = self.align.get(i).copied().unwrap_or_default();
+                let (before, after) = align.split(padding);
+                out.push_str(&" ".repeat(before), fill);
+                out.extend(content.spans().iter().cloned());
+                out.push_str(&" ".repeat(after), fill);
+            }
+            if let Some(chars) = &chars {
+                out.push_str(" ", fill);
+                out.push_str(chars.vertical, self.border_style);
+            }
+        }
+    }
+
+    /// Lays out a cell into lines of at most `width` cells, layering its styles on top of `style`.
+    fn cell_lines(&self, cell: &StyledString, style: Style, width: usize) -> Vec<StyledString> {
+        let cell: StyledString = cell
+            .spans()
+            .iter()
+            .map(|span| span.clone().with_style(style.patch(span.style())))
+            .collect();
+        let mut lines = Vec::new();
+        for line in cell.lines() {
+            let formatted = line.format_sequence();
+            match self.overflow {
+                Overflow::Truncate => {
+                    lines.push(parse_ansi(&truncate_to_width(&formatted, width, "…")))
+                }
+                Overflow::Wrap => {
+                    for wrapped in parse_ansi(&wrap(&formatted, width)).lines() {
+                        // a character wider than the column still sticks out after wrapping
+                        lines.push(if wrapped.width() > width {
+                            parse_ansi(&truncate_to_width(&wrapped.format_sequence(), width, "…"))
+                        } else {
+                            wrapped
+                        });
+                    }
+                }
+            }
+        }
+        lines
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+/// Returns `width`, or the width of the terminal, or 80 columns if it's unknown, e.g. because the
+/// output is piped.
+pub(crate) fn max_width(width: Option<usize>) -> usize {
+    width
+        .or_else(|| terminal_size().map(|(columns, _)| columns))
+        .unwrap_or(80)
+}
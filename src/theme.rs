@@ -0,0 +1,236 @@
+//! Prompt Themes
+//!
+//! This module provides the [`Theme`] used by the prompts of the [`input`](crate::input) module,
+//! covering every glyph and style they print, and the colors of values pretty-printed with
+//! `styled::pretty()`. A theme can be set for the whole program with [`set_theme`] or for a few
+//! prompts only with [`with_theme`].
+
+use std::{cell::RefCell, sync::RwLock};
+
+use crate::styled::{Color, Style, StyledText};
+
+/// The theme set with [`set_theme`], `None` until one is set.
+static THEME: RwLock<Option<Theme>> = RwLock::new(None);
+
+thread_local! {
+    /// The theme set with [`with_theme`] for the current thread.
+    static SCOPED: RefCell<Option<Theme>> = const { RefCell::new(None) };
+}
+
+/// The glyphs and styles used by the prompts and pretty-printed values.
+///
+/// Custom themes are best built on top of an existing one:
+///
+/// ```rust
+/// use console_utils::{
+///     styled::{Color, Style, StyledText},
+///     theme::{set_theme, Theme},
+/// };
+///
+/// set_theme(Theme {
+///     prompt_prefix: StyledText::new("◆").fg(Color::Magenta),
+///     active_item_style: Style::new().fg(Color::Magenta).bold(),
+///     ..Theme::default()
+/// });
+/// ```
+///
+/// With the `serde` feature, themes can be loaded from configuration files. Missing fields keep
+/// their [default](Theme::default) value, glyphs are either plain strings or tables with `text`,
+/// `style` and `link`:
+///
+/// ```toml
+/// prompt_prefix = { text = "◆", style = { fg = "magenta", bold = true } }
+/// prompt_suffix = "»"
+/// active_item_style = { fg = "#ff8800", underline = "curly" }
+/// checked_item_style = { fg = "ansi(208)" }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct Theme {
+    /// Printed before the question of a prompt, a red `?` by default.
+    pub prompt_prefix: StyledText<'static>,
+    /// The style of the question itself.
+    pub prompt_style: Style,
+    /// Printed after the question, where the answer starts, a gray `›` by default.
+    pub prompt_suffix: StyledText<'static>,
+    /// The style of hints in the question, like the default answer, dim by default.
+    pub hint_style: Style,
+    /// Printed before the message of invalid input, a red `X` by default.
+    pub error_prefix: StyledText<'static>,
+    /// The style of the message of invalid input.
+    pub error_style: Style,
+    /// Marks the option under the cursor of a selection, a green `›` by default.
+    pub active_item_prefix: StyledText<'static>,
+    /// Printed before all other options, padded to the width of the
+    /// [`active_item_prefix`](Theme::active_item_prefix).
+    pub inactive_item_prefix: StyledText<'static>,
+    /// The style of the option under the cursor, cyan by default.
+    pub active_item_style: Style,
+    /// The style of all other options.
+    pub item_style: Style,
+    /// The style of options checked in a multiselect, green by default. Takes precedence over
+    /// the [`active_item_style`](Theme::active_item_style).
+    pub checked_item_style: Style,
+    /// Printed before options checked in a multiselect, nothing by default.
+    pub checked_item_prefix: StyledText<'static>,
+    /// Printed before options not checked in a multiselect, padded to the width of the
+    /// [`checked_item_prefix`](Theme::checked_item_prefix). Nothing by default.
+    pub unchecked_item_prefix: StyledText<'static>,
+    /// The style of the characters of options matching the filter typed in a select, patching
+    /// the style of the option. Bold yellow by default.
+    pub match_style: Style,
+    /// Shown above the options of a scrolled list when more are above, a dim `↑` by default.
+    pub scroll_up_indicator: StyledText<'static>,
+    /// Shown below the options of a scrolled list when more are below, a dim `↓` by default.
+    pub scroll_down_indicator: StyledText<'static>,
+    /// The style of the keys of pretty-printed values, blue by default.
+    pub value_key_style: Style,
+    /// The style of pretty-printed strings, green by default.
+    pub value_string_style: Style,
+    /// The style of pretty-printed numbers, yellow by default.
+    pub value_number_style: Style,
+    /// The style of pretty-printed booleans, magenta by default.
+    pub value_bool_style: Style,
+    /// The style of pretty-printed `null`s, gray by default.
+    pub value_null_style: Style,
+}
+
+impl Default for Theme {
+    /// The default theme, with a red `?`, a gray `›` and cyan highlighting.
+    fn default() -> Self {
+        Self {
+            prompt_prefix: StyledText::new("?").fg(Color::Red),
+            prompt_style: Style::new(),
+            prompt_suffix: StyledText::new("›").fg(Color::BrightBlack),
+            hint_style: Style::new().dim(),
+            error_prefix: StyledText::new("X").fg(Color::Red),
+            error_style: Style::new(),
+            active_item_prefix: StyledText::new("›").fg(Color::Green),
+            inactive_item_prefix: StyledText::new(""),
+            active_item_style: Style::new().fg(Color::Cyan),
+            item_style: Style::new(),
+            checked_item_style: Style::new().fg(Color::Green),
+            checked_item_prefix: StyledText::new(""),
+            unchecked_item_prefix: StyledText::new(""),
+            match_style: Style::new().fg(Color::Yellow).bold(),
+            scroll_up_indicator: StyledText::new("↑").dim(),
+            scroll_down_indicator: StyledText::new("↓").dim(),
+            value_key_style: Style::new().fg(Color::Blue),
+            value_string_style: Style::new().fg(Color::Green),
+            value_number_style: Style::new().fg(Color::Yellow),
+            value_bool_style: Style::new().fg(Color::Magenta),
+            value_null_style: Style::new().fg(Color::BrightBlack),
+        }
+    }
+}
+
+impl Theme {
+    /// A theme using ASCII characters only and no colors, for terminals lacking Unicode fonts
+    /// or output that ends up in log files.
+    ///
+    /// The option under the cursor and keys of values are bold, checked options are marked with
+    /// `[x]`.
+    pub fn minimal() -> Self {
+        Self {
+            prompt_prefix: StyledText::new("?"),
+            prompt_style: Style::new(),
+            prompt_suffix: StyledText::new(">"),
+            hint_style: Style::new(),
+            error_prefix: StyledText::new("!"),
+            error_style: Style::new(),
+            active_item_prefix: StyledText::new(">"),
+            inactive_item_prefix: StyledText::new(""),
+            active_item_style: Style::new().bold(),
+            item_style: Style::new(),
+            checked_item_style: Style::new(),
+            checked_item_prefix: StyledText::new("[x]"),
+            unchecked_item_prefix: StyledText::new("[ ]"),
+            match_style: Style::new().underline(),
+            scroll_up_indicator: StyledText::new("^"),
+            scroll_down_indicator: StyledText::new("v"),
+            value_key_style: Style::new().bold(),
+            value_string_style: Style::new(),
+            value_number_style: Style::new(),
+            value_bool_style: Style::new(),
+            value_null_style: Style::new(),
+        }
+    }
+
+    /// A colorful theme with bold questions and circles marking the checked options.
+    pub fn colorful() -> Self {
+        Self {
+            prompt_prefix: StyledText::new("?").fg(Color::BrightMagenta).bold(),
+            prompt_style: Style::new().bold(),
+            prompt_suffix: StyledText::new("❯").fg(Color::BrightCyan),
+            hint_style: Style::new().fg(Color::BrightBlack),
+            error_prefix: StyledText::new("✖").fg(Color::BrightRed),
+            error_style: Style::new().fg(Color::BrightRed),
+            active_item_prefix: StyledText::new("❯").fg(Color::BrightMagenta),
+            inactive_item_prefix: StyledText::new(""),
+            active_item_style: Style::new().fg(Color::BrightCyan).bold(),
+            item_style: Style::new(),
+            checked_item_style: Style::new().fg(Color::BrightGreen),
+            checked_item_prefix: StyledText::new("◉").fg(Color::BrightGreen),
+            unchecked_item_prefix: StyledText::new("◯").fg(Color::BrightBlack),
+            match_style: Style::new().fg(Color::BrightYellow).bold(),
+            scroll_up_indicator: StyledText::new("▲").fg(Color::BrightBlack),
+            scroll_down_indicator: StyledText::new("▼").fg(Color::BrightBlack),
+            value_key_style: Style::new().fg(Color::BrightBlue).bold(),
+            value_string_style: Style::new().fg(Color::BrightGreen),
+            value_number_style: Style::new().fg(Color::BrightYellow),
+            value_bool_style: Style::new().fg(Color::BrightMagenta),
+            value_null_style: Style::new().fg(Color::BrightBlack).italic(),
+        }
+    }
+}
+
+/// Returns the theme used by the prompts.
+///
+/// This is the theme of the innermost [`with_theme`] call on the current thread, otherwise the
+/// one set with [`set_theme`], or the [default](Theme::default) one.
+pub fn theme() -> Theme {
+    if let Some(theme) = SCOPED.with(|scoped| scoped.borrow().clone()) {
+        return theme;
+    }
+    THEME
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_default()
+}
+
+/// Sets the theme used by all prompts of the program.
+pub fn set_theme(theme: Theme) {
+    *THEME.write().unwrap_or_else(|e| e.into_inner()) = Some(theme);
+}
+
+/// Runs `f` with `theme` used by all prompts it shows on the current thread, overriding the one
+/// set with [`set_theme`].
+///
+/// # Example
+///
+/// ```no_run
+/// use console_utils::{
+///     input::select,
+///     theme::{with_theme, Theme},
+/// };
+///
+/// let choice = with_theme(&Theme::minimal(), || select("Pick one", &["a", "b"]));
+/// ```
+pub fn with_theme<R>(theme: &Theme, f: impl FnOnce() -> R) -> R {
+    /// Restores the previous theme, even if `f` panics.
+    struct Restore(Option<Theme>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SCOPED.with(|scoped| *scoped.borrow_mut() = self.0.take());
+        }
+    }
+
+    let _restore = Restore(SCOPED.with(|scoped| scoped.borrow_mut().replace(theme.clone())));
+    f()
+}
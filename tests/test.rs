@@ -2,10 +2,26 @@ use std::{thread, time::Duration};
 
 // Import the functions to be tested from the crate root
 use console_utils::{
-    control::{clear_line, flush, move_cursor_down, move_cursor_up, Visibility},
-    input::{input, multiselect, reveal, select, spinner, Empty, SpinnerType},
+    control::{
+        clear_line, enable_ansi_support, flush, move_cursor_down, move_cursor_up, terminal_size,
+        Visibility,
+    },
+    diagnostic::{Diagnostic, Label, Language, Marker, Severity},
+    input::{
+        confirm, fuzzy_match, input, multiselect, password, reveal, select, spinner, Completer,
+        Empty, History, Input, MultiSelect, Password, PathCompleter, Select, SpinnerType,
+        WordCompleter,
+    },
+    markup,
+    panel::{panel, rule, Panel, Rule},
     read::{read_key, Key},
-    styled::{Color, StyledText},
+    styled::{
+        display_width, gradient, markup, parse_ansi, strip_ansi, truncate_to_width, wrap,
+        Capabilities, Color, ColorLevel, Gradient, Html, Link, MarkupErrorKind, Palette, Style,
+        StyledString, StyledText, Svg, Underline, Wrap,
+    },
+    table::{Align, Border, Overflow, Table},
+    theme::{theme, with_theme, Theme},
 };
 
 #[test]
@@ -46,6 +62,268 @@ fn user_select() {
     println!("{:?}", result);
 }
 
+#[test]
+#[ignore = "user inputs"]
+fn user_line_editing() {
+    // Type "world", press Home, type "hello ", press End and Ctrl-W
+    let result = input::<String>("Edit the line");
+    assert_eq!(result, "hello ");
+}
+
+#[test]
+#[ignore = "user inputs"]
+fn user_history() {
+    // Press Up twice and Enter, then Ctrl-R, type "2" and press Enter
+    let mut history = History::new();
+    history.push("8080");
+    history.push("3000");
+    let port: u16 = Input::new("Port").history(&mut history).read();
+    assert_eq!(port, 8080);
+    let port: u16 = Input::new("Port").history(&mut history).read();
+    assert_eq!(port, 3000);
+}
+
+#[test]
+fn history() {
+    let mut history = History::new().max_size(3);
+    for entry in ["a", "b", "a", " ", "c", "d"] {
+        history.push(entry);
+    }
+    assert_eq!(history.entries(), ["a", "c", "d"]);
+
+    let mut repeated = History::new().dedup(false);
+    for entry in ["a", "a", "b", "a"] {
+        repeated.push(entry);
+    }
+    assert_eq!(repeated.entries(), ["a", "b", "a"]);
+
+    history.push_in("port", "8080");
+    history.push_in("port", "tab\there\\\nnewline");
+    assert_eq!(history.entries_in("host"), [] as [String; 0]);
+
+    let path = std::env::temp_dir().join(format!("console-utils-history-{}", std::process::id()));
+    history.save(&path).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "a\nc\nd\nport\t8080\nport\ttab\\there\\\\\\nnewline\n"
+    );
+    assert_eq!(History::load(&path).unwrap().max_size(3), history);
+    assert_eq!(History::load(&path).unwrap().max_size(1).entries(), ["d"]);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(History::load(&path).unwrap(), History::new());
+}
+
+#[test]
+fn completion() {
+    let words = WordCompleter::new(["select", "spinner", "reveal"]);
+    assert_eq!(
+        words.complete("cmd s", 5),
+        (4, vec!["select".to_string(), "spinner".to_string()])
+    );
+    assert_eq!(words.complete("cmd x", 5), (4, Vec::new()));
+    assert_eq!(words.complete("r tail", 1), (0, vec!["reveal".to_string()]));
+
+    let dir = std::env::temp_dir().join(format!("console-utils-paths-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("docs")).unwrap();
+    for file in ["data.csv", "dump.sql", ".hidden"] {
+        std::fs::write(dir.join(file), "").unwrap();
+    }
+    let base = format!("{}{}", dir.display(), std::path::MAIN_SEPARATOR);
+    let line = format!("open {base}d");
+    let (start, candidates) = PathCompleter::new().complete(&line, line.len());
+    assert_eq!(start, 5);
+    let sep = std::path::MAIN_SEPARATOR;
+    assert_eq!(
+        candidates,
+        [
+            format!("{base}data.csv"),
+            format!("{base}docs{sep}"),
+            format!("{base}dump.sql")
+        ]
+    );
+    let line = format!("{base}.");
+    let (_, candidates) = PathCompleter::new().complete(&line, line.len());
+    assert_eq!(candidates, [format!("{base}.hidden")]);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let upper = |line: &str, cursor: usize| (0, vec![line[..cursor].to_uppercase()]);
+    assert_eq!(upper.complete("abc", 2), (0, vec!["AB".to_string()]));
+}
+
+#[test]
+#[ignore = "user inputs"]
+fn user_completion() {
+    // Type "s", press Tab twice to list the candidates, type "p" and press Tab and Enter
+    let result: String = Input::new("Function")
+        .completer(WordCompleter::new(["select", "spinner", "reveal"]))
+        .read();
+    assert_eq!(result, "spinner");
+}
+
+#[test]
+#[ignore = "user inputs"]
+fn user_validation() {
+    // Enter "80", then "abc", then "8080"
+    let port: u16 = Input::new("Port")
+        .validate(|v: &u16| {
+            (1024..)
+                .contains(v)
+                .then_some(())
+                .ok_or("must be 1024 or more")
+        })
+        .read();
+    assert_eq!(port, 8080);
+}
+
+#[test]
+#[ignore = "user inputs"]
+fn user_defaults() {
+    // Press Enter twice
+    let port: u16 = Input::new("Port").default(8080).read();
+    assert_eq!(port, 8080);
+    let name: String = Input::new("Project").initial_text("my-app").read();
+    assert_eq!(name, "my-app");
+}
+
+#[test]
+#[ignore = "user inputs"]
+fn user_password() {
+    // Type "secret" twice, then "1234"
+    let secret = Password::new("Password").confirm("Repeat").read();
+    assert_eq!(secret.expose(), "secret");
+    assert_eq!(format!("{secret:?}"), "Secret(***)");
+    assert_eq!(password("Pin").expose(), "1234");
+}
+
+#[test]
+#[ignore = "user inputs"]
+fn user_confirm() {
+    // Press Enter, then "Y"
+    assert!(!confirm("Continue?", Some(false)));
+    assert!(confirm("Really?", None));
+}
+
+#[test]
+#[ignore = "user inputs"]
+fn user_scrolling() {
+    // Press End and Enter, then PageDown, Space and Enter
+    let options: Vec<String> = (0..50).map(|i| format!("Option {i}")).collect();
+    let options: Vec<&str> = options.iter().map(String::as_str).collect();
+    assert_eq!(Select::new("Pick one", 0..50).page_size(5).read(), 49);
+    let selected = MultiSelect::new("Pick some", &options).page_size(5).read();
+    assert_eq!(selected.iter().position(|&s| s), Some(5));
+}
+
+#[test]
+fn fuzzy_matching() {
+    assert_eq!(fuzzy_match("", "anything"), Some((0, Vec::new())));
+    assert_eq!(fuzzy_match("abc", "ab"), None);
+    assert_eq!(fuzzy_match("ba", "abc"), None);
+
+    // smart case
+    assert!(fuzzy_match("readme", "README.md").is_some());
+    assert!(fuzzy_match("README", "readme.md").is_none());
+
+    // the best alignment, not the first one
+    assert_eq!(fuzzy_match("fbar", "foo-bar").unwrap().1, [0, 4, 5, 6]);
+    assert_eq!(fuzzy_match("ab", "xaxab").unwrap().1, [3, 4]);
+    assert_eq!(fuzzy_match("mr", "my-repo").unwrap().1, [0, 3]);
+
+    let score = |pattern, text| fuzzy_match(pattern, text).unwrap().0;
+    // consecutive beats scattered, boundaries and camel case beat the middle of words
+    assert!(score("sel", "select") > score("sel", "s-e-l"));
+    assert!(score("fb", "foo-bar") > score("fb", "fizzbuzz"));
+    assert!(score("fb", "fooBar") > score("fb", "fizzbuzz"));
+    assert!(score("con", "console") > score("con", "falcon"));
+}
+
+#[test]
+#[ignore = "user inputs"]
+fn user_fuzzy_select() {
+    // Type "fs" and press Enter
+    let branches = ["main", "feature/login", "fix/select-scroll", "docs"];
+    assert_eq!(select("Branch", &branches), 2);
+}
+
+#[test]
+#[ignore = "user inputs"]
+fn user_select_items() {
+    // Type "db" and press Enter twice
+    #[derive(Debug, PartialEq)]
+    struct Host(&'static str, u16);
+    let hosts = vec![Host("web", 80), Host("db", 5432)];
+    let host = Select::with_label("Host", &hosts, |host| format!("{}:{}", host.0, host.1)).read();
+    assert_eq!(host, &Host("db", 5432));
+    let name: String = Select::new("Name", ["a".to_string(), "b".to_string()]).read();
+    assert_eq!(name, "a");
+}
+
+#[test]
+#[should_panic(expected = "at least one item")]
+fn select_without_items() {
+    assert_eq!(select("Nothing", &[]), 0);
+    Select::new("Nothing", Vec::<u8>::new());
+}
+
+/// Set in the child processes of `run_with_input`.
+const CHILD: &str = "CONSOLE_UTILS_TEST_CHILD";
+
+/// Runs the `test` again in a child process reading `input`, returns its output, or `None` in
+/// the child process itself.
+fn run_with_input(test: &str, input: &[u8]) -> Option<std::process::Output> {
+    use std::{io::Write, process};
+
+    if std::env::var_os(CHILD).is_some() {
+        return None;
+    }
+    let mut child = process::Command::new(std::env::current_exe().unwrap())
+        .args([test, "--exact", "--nocapture"])
+        .env(CHILD, "1")
+        .stdin(process::Stdio::piped())
+        .stdout(process::Stdio::piped())
+        .stderr(process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "{output:?}");
+    Some(output)
+}
+
+#[test]
+fn closed_input() {
+    if let Some(output) = run_with_input("closed_input", b"") {
+        // the prompt given its own theme
+        let minimal = format!("Port (8080) {}", Theme::minimal().prompt_suffix.text());
+        assert!(String::from_utf8_lossy(&output.stdout).contains(&minimal));
+        return;
+    }
+
+    // the prompts give up instead of asking again
+    let error = Input::<u16>::new("Port")
+        .validate(|port| if *port > 1024 { Ok(()) } else { Err("too low") })
+        .try_read()
+        .unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::UnexpectedEof);
+    let theme = Theme::minimal();
+    let port = Input::new("Port").default(8080).theme(&theme).try_read();
+    assert_eq!(port.unwrap(), 8080);
+    assert!(confirm("Continue?", Some(true)));
+    assert!(!confirm("Continue?", Some(false)));
+    assert!(std::panic::catch_unwind(|| confirm("Continue?", None)).is_err());
+}
+
+#[test]
+fn piped_password() {
+    let input = ["sécret\r\n日本\n".as_bytes(), b"\xff\n"].concat();
+    if run_with_input("piped_password", &input).is_some() {
+        return;
+    }
+    assert_eq!(password("Password").expose(), "sécret");
+    let pin = Password::new("Pin").confirm("Repeat").try_read();
+    assert_eq!(pin.unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+}
+
 #[test]
 #[ignore = "user inputs"]
 fn user_read_key() {
@@ -126,6 +404,175 @@ fn r#move() {
     clear_line();
 }
 
+#[test]
+fn ansi_support() {
+    let supported = enable_ansi_support();
+    // only enabled once
+    assert_eq!(enable_ansi_support(), supported);
+    #[cfg(not(windows))]
+    assert!(supported);
+    if !supported {
+        assert_eq!(Capabilities::detect().colors, ColorLevel::None);
+    }
+}
+
+#[test]
+fn themes() {
+    assert_eq!(theme(), Theme::default());
+    assert_eq!(theme().prompt_prefix.text(), "?");
+
+    // scoped themes nest and are restored afterwards, without touching the global one
+    let inner = with_theme(&Theme::minimal(), || {
+        assert_eq!(with_theme(&Theme::colorful(), theme), Theme::colorful());
+        theme()
+    });
+    assert_eq!(inner, Theme::minimal());
+    assert_eq!(theme(), Theme::default());
+
+    // the minimal theme is ASCII-only
+    let minimal = Theme::minimal();
+    assert!([
+        &minimal.prompt_prefix,
+        &minimal.prompt_suffix,
+        &minimal.error_prefix,
+        &minimal.active_item_prefix,
+        &minimal.checked_item_prefix,
+        &minimal.unchecked_item_prefix,
+    ]
+    .iter()
+    .all(|glyph| glyph.text().is_ascii() && glyph.style().foreground().is_none()));
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn theme_config() {
+    let theme: Theme = toml::from_str(
+        r##"
+        prompt_prefix = { text = "◆", style = { fg = "bright-magenta", bold = true } }
+        prompt_suffix = "»"
+        active_item_style = { fg = "#ff8800", underline = "curly", underline_color = "ansi(208)" }
+        "##,
+    )
+    .unwrap();
+    assert_eq!(
+        theme.prompt_prefix,
+        StyledText::new("◆").fg(Color::BrightMagenta).bold()
+    );
+    assert_eq!(theme.prompt_suffix, StyledText::new("»"));
+    assert_eq!(
+        theme.active_item_style,
+        Style::new()
+            .fg(Color::Rgb(0xff, 0x88, 0))
+            .underline_style(Underline::Curly)
+            .underline_color(Color::Indexed(208))
+    );
+    // everything else keeps its default
+    assert_eq!(theme.error_prefix, Theme::default().error_prefix);
+
+    // round trips
+    let colorful = Theme::colorful();
+    assert_eq!(
+        toml::from_str::<Theme>(&toml::to_string(&colorful).unwrap()).unwrap(),
+        colorful
+    );
+    let palette = Palette::new()
+        .color(Color::Red, (1, 2, 3))
+        .background((4, 5, 6));
+    assert_eq!(
+        toml::from_str::<Palette>(&toml::to_string(&palette).unwrap()).unwrap(),
+        palette
+    );
+
+    // readable errors
+    let err = toml::from_str::<Theme>(r#"item_style = { fg = "sparkly" }"#).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("invalid color `sparkly`, expected a color name"));
+    let err = toml::from_str::<Theme>(r#"item_style = { fg = "ansi(256)" }"#).unwrap_err();
+    assert!(err.to_string().contains("invalid color `ansi(256)`"));
+    let err = toml::from_str::<Theme>(r#"item_style = { blod = true }"#).unwrap_err();
+    assert!(err.to_string().contains("unknown field `blod`"));
+    let err = toml::from_str::<Palette>(r##"purple = "#800080""##).unwrap_err();
+    assert!(err.to_string().contains("unknown palette entry `purple`"));
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn pretty_printing() {
+    use console_utils::styled::pretty;
+    use serde::Serialize;
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize)]
+    enum Mode {
+        Fast,
+        Limited { jobs: u8 },
+    }
+
+    #[derive(Serialize)]
+    struct Record {
+        id: u64,
+        ratio: f32,
+        mode: Mode,
+        fallback: Mode,
+        env: BTreeMap<&'static str, &'static str>,
+        points: Vec<(i32, i32)>,
+        empty: Vec<u8>,
+    }
+
+    let record = Record {
+        id: 7,
+        ratio: 0.5,
+        mode: Mode::Fast,
+        fallback: Mode::Limited { jobs: 4 },
+        env: [("PATH", "/bin"), ("no color", "1")].into_iter().collect(),
+        points: vec![(0, 0), (10, -3)],
+        empty: vec![],
+    };
+    let printed = with_theme(&Theme::default(), || pretty(&record).unwrap().render());
+    assert_eq!(
+        printed.plain_text(),
+        "{\n  \
+           id: 7,\n  \
+           ratio: 0.5,\n  \
+           mode: \"Fast\",\n  \
+           fallback: {\n    \
+             Limited: {\n      \
+               jobs: 4\n    \
+             }\n  \
+           },\n  \
+           env: {\n    \
+             PATH: \"/bin\",\n    \
+             \"no color\": \"1\"\n  \
+           },\n  \
+           points: [[0, 0], [10, -3]],\n  \
+           empty: []\n\
+         }"
+    );
+    assert!(printed
+        .spans()
+        .contains(&StyledText::new("id").fg(Color::Blue).into_owned()));
+    assert!(printed
+        .spans()
+        .contains(&StyledText::new("7").fg(Color::Yellow).into_owned()));
+
+    // arrays too wide for the line are unfolded
+    let narrow = pretty(&[vec!["alpha", "beta"], vec!["gamma"]])
+        .unwrap()
+        .indent(4)
+        .width(22);
+    assert_eq!(
+        narrow.render().plain_text(),
+        "[\n    [\"alpha\", \"beta\"],\n    [\"gamma\"]\n]"
+    );
+    assert_eq!(pretty(&None::<u8>).unwrap().render().plain_text(), "null");
+
+    // custom serialization errors are passed on
+    let err = pretty(&Color::ANSI(38)).unwrap_err();
+    assert!(err.to_string().contains("has no color name"));
+    println!("{}", pretty(&record).unwrap());
+}
+
 #[test]
 fn color() {
     // fg
@@ -158,3 +605,548 @@ fn color() {
             .blink()
     );
 }
+
+#[test]
+fn markup_spans() {
+    let text = markup("[bold red]Error:[/] file [underline on #ff8800]x[[1][/] missing").unwrap();
+    let spans = text.spans();
+    assert_eq!(spans.len(), 4);
+    assert_eq!(spans[0].text(), "Error:");
+    assert_eq!(spans[0].style(), Style::new().fg(Color::Red).bold());
+    assert_eq!(spans[2].text(), "x[1]");
+    assert_eq!(
+        spans[2].style(),
+        Style::new().bg(Color::Rgb(0xff, 0x88, 0)).underline()
+    );
+    assert_eq!(text.plain_text(), "Error: file x[1] missing");
+
+    // nested tags inherit the outer style
+    let nested = markup("[red]a[bold]b[/bold]c[/]").unwrap();
+    assert_eq!(
+        nested.spans()[1].style(),
+        Style::new().fg(Color::Red).bold()
+    );
+    assert_eq!(nested.spans()[2].style(), Style::new().fg(Color::Red));
+
+    // palette indices
+    assert_eq!("ansi(208)".parse(), Ok(Color::Indexed(208)));
+    assert!("ansi(256)".parse::<Color>().is_err());
+    assert_eq!(
+        markup("[ansi(9)]x[/]").unwrap().spans()[0].style(),
+        Style::new().fg(Color::Indexed(9))
+    );
+
+    // compile time checked and formatted
+    let checked = markup!("[bright-black]{}[/] {{ok}}", 42);
+    assert_eq!(checked.plain_text(), "42 {ok}");
+    println!("{text} {checked}");
+}
+
+#[test]
+fn markup_errors() {
+    let err = |s| markup(s).unwrap_err().kind();
+    assert_eq!(err("[bold]x"), MarkupErrorKind::UnclosedTag);
+    assert_eq!(err("x[/]"), MarkupErrorKind::UnexpectedClose);
+    assert_eq!(err("[bold]x[/red]"), MarkupErrorKind::MismatchedClose);
+    assert_eq!(err("[bold x"), MarkupErrorKind::UnterminatedTag);
+    assert_eq!(err("[bold sparkly]x[/]"), MarkupErrorKind::UnknownStyle);
+    assert_eq!(err("[red on]x[/]"), MarkupErrorKind::MissingBackground);
+    assert_eq!(err("[]"), MarkupErrorKind::EmptyTag);
+    assert_eq!(
+        markup("[bold sparkly]x[/]").unwrap_err().to_string(),
+        "unknown style: `sparkly` at byte 6"
+    );
+}
+
+#[test]
+fn ansi_width() {
+    let text = markup("[red]日本[/] [bold]e\u{301}👨\u{200d}👩\u{200d}👧[/]")
+        .unwrap()
+        .format_sequence();
+    assert_eq!(strip_ansi(&text), "日本 e\u{301}👨\u{200d}👩\u{200d}👧");
+    assert_eq!(display_width(&text), 8);
+
+    // the cut never splits a wide character and keeps the trailing reset
+    let cut = truncate_to_width(&text, 4, "…");
+    assert_eq!(cut, "\x1b[31m日…\x1b[0m\x1b[1m\x1b[0m");
+    assert_eq!(display_width(&cut), 3);
+    assert_eq!(truncate_to_width(&text, 8, "…"), text);
+}
+
+#[test]
+fn ansi_round_trip() {
+    let mut text = markup("[bold red]Error:[/] [italic on #102030]rgb[/] plain [u s]x[/]").unwrap();
+    text.push(
+        StyledText::new("indexed")
+            .fg(Color::Indexed(208))
+            .bg(Color::BrightBlack)
+            .blink()
+            .reverse(),
+    );
+    assert_eq!(parse_ansi(&text.format_sequence()), text);
+
+    // foreign output: colon forms, partial resets and non-SGR sequences
+    let parsed = parse_ansi("\x1b[1;38:2::1:2:3mA\x1b[22mB\x1b[2K\x1b[39;48;5;4mC\x1b[m");
+    let mut expected = StyledString::new();
+    expected.push_str("A", Style::new().fg(Color::Rgb(1, 2, 3)).bold());
+    expected.push_str("B", Style::new().fg(Color::Rgb(1, 2, 3)));
+    expected.push_str("C", Style::new().bg(Color::Indexed(4)));
+    assert_eq!(parsed, expected);
+}
+
+#[test]
+fn export_html_svg() {
+    let text = markup("[bold bright-black]a<b[/] [reverse red on #000080]c[/]\n[u s]d[/]").unwrap();
+
+    let html = Html::new().render(&text);
+    assert_eq!(
+        html,
+        "<pre style=\"color:#cccccc;background-color:#1e1e1e\">\
+         <span style=\"color:#666666;font-weight:bold\">a&lt;b</span> \
+         <span style=\"color:#000080;background-color:#cd3131\">c</span>\n\
+         <span style=\"text-decoration:underline line-through\">d</span></pre>"
+    );
+
+    let palette = Palette::new().color(Color::BrightBlack, (0x80, 0x80, 0x80));
+    let html = Html::new().palette(palette).classes("t");
+    assert!(html
+        .render(&text)
+        .contains(r#"<span class="t-fg-bright-black t-bold">a&lt;b</span>"#));
+    assert!(html
+        .stylesheet()
+        .contains(".t-fg-bright-black { color: #808080; }"));
+
+    let svg = Svg::new().title("demo").render(&text);
+    assert!(svg.starts_with("<svg") && svg.ends_with("</svg>"));
+    assert!(svg.contains(r##"fill="#666666" font-weight="bold">a&lt;b</tspan>"##));
+    assert!(svg.contains(r#"text-decoration="underline line-through">d</tspan>"#));
+    // wide characters take two cells, combined ones share theirs
+    let svg = Svg::new().font_size(10.0).render(&"日本e\u{301}x".into());
+    assert!(svg.contains(r#"<tspan x="10.0 22.0 34.0 34.0 40.0" "#));
+}
+
+#[test]
+fn extended_attributes() {
+    let text = StyledText::new("token")
+        .fg(Color::Red)
+        .dim()
+        .underline_style(Underline::Curly)
+        .underline_color(Color::Rgb(255, 0, 0))
+        .hidden()
+        .overline();
+    assert_eq!(
+        text.format_sequence(),
+        "\x1b[31;2;4:3;58;2;255;0;0;8;53mtoken\x1b[0m"
+    );
+    // terminals without extended underlines get a plain one in the text color
+    assert_eq!(
+        text.format_for(&Capabilities::basic()),
+        "\x1b[31;2;4;8;53mtoken\x1b[0m"
+    );
+
+    let mut all =
+        markup("[dim uu]a[/] [curly-underline]b[/] [dotted-underline overline]c[/]").unwrap();
+    all.push(
+        StyledText::new("d")
+            .underline()
+            .underline_color(Color::BrightBlue),
+    );
+    all.push(text);
+    assert_eq!(parse_ansi(&all.format_sequence()), all);
+    println!("{all}");
+}
+
+#[test]
+fn hyperlinks() {
+    let text = StyledText::new("docs")
+        .fg(Color::Blue)
+        .link(Link::new("https://docs.rs/\x1b").with_id("a;b"));
+    assert_eq!(text.hyperlink().unwrap().url(), "https://docs.rs/");
+    assert_eq!(
+        text.format_sequence(),
+        "\x1b]8;id=ab;https://docs.rs/\x1b\\\x1b[34mdocs\x1b[0m\x1b]8;;\x1b\\"
+    );
+    // suppressed without support or with colors disabled
+    assert_eq!(
+        text.format_for(&Capabilities::basic()),
+        "\x1b[34mdocs\x1b[0m"
+    );
+    let no_color = Capabilities {
+        colors: ColorLevel::None,
+        ..Capabilities::all()
+    };
+    assert_eq!(text.format_for(&no_color), "docs");
+
+    let mut all = StyledString::from(text.clone());
+    all.push(StyledText::new(" and ").link("https://docs.rs/"));
+    all.push(StyledText::new("more"));
+    assert_eq!(all.spans().len(), 3);
+    assert_eq!(strip_ansi(&all.format_sequence()), "docs and more");
+    assert_eq!(display_width(&all.format_sequence()), 13);
+    assert_eq!(parse_ansi(&all.format_sequence()), all);
+    assert!(Html::new()
+        .render(&all)
+        .contains(r#"<a href="https://docs.rs/"> and </a>more"#));
+    println!("{all}");
+}
+
+#[test]
+fn wrapping() {
+    assert_eq!(
+        wrap("The quick brown fox  jumps\nover the lazy dog", 10),
+        "The quick\nbrown fox\njumps\nover the\nlazy dog"
+    );
+    // overlong words are broken, wide characters never split
+    assert_eq!(wrap("a verylongword", 5), "a\nveryl\nongwo\nrd");
+    assert_eq!(wrap("日本語テキスト", 5), "日本\n語テ\nキス\nト");
+    // wider than the whole line, on a line of its own
+    assert_eq!(wrap("a日本 b", 1), "a\n日\n本\nb");
+
+    // styles are interrupted for the indentation only
+    let text = markup("[red]one two[/] three").unwrap().to_string();
+    let wrapped = Wrap::new(9).indent("> ").hanging_indent("  ").wrap(&text);
+    assert_eq!(strip_ansi(&wrapped), "> one two\n>   three");
+    let styled = StyledText::new("one two four").fg(Color::Red);
+    assert_eq!(
+        Wrap::new(6).indent("|").wrap(&styled.format_sequence()),
+        "|\x1b[31mone\x1b[0m\n|\x1b[31mtwo\x1b[0m\n|\x1b[31mfour\x1b[0m"
+    );
+    let linked = StyledText::new("a b").link("https://docs.rs");
+    assert_eq!(
+        parse_ansi(&Wrap::new(2).indent(" ").wrap(&linked.format_sequence())),
+        parse_ansi(&format!(
+            " {}\n {}",
+            StyledText::new("a")
+                .link("https://docs.rs")
+                .format_sequence(),
+            StyledText::new("b")
+                .link("https://docs.rs")
+                .format_sequence()
+        ))
+    );
+    assert!(wrapped.split('\n').all(|line| display_width(line) <= 9));
+
+    if let Some((columns, rows)) = terminal_size() {
+        assert!(columns > 0 && rows > 0);
+    }
+}
+
+#[test]
+fn tables() {
+    let table = Table::new()
+        .header(["Name", "Description", "n"])
+        .row([
+            StyledText::new("日本").fg(Color::Red).into(),
+            StyledString::from("a rather long description"),
+            "1".into(),
+        ])
+        .row(["x", "short\nmulti", "22"])
+        .align(2, Align::Right)
+        .max_width(30)
+        .stripes(Style::new().bg(Color::BrightBlack));
+    assert_eq!(
+        table.render().plain_text(),
+        "╭──────┬────────────────┬────╮\n\
+         │ Name │ Description    │  n │\n\
+         ├──────┼────────────────┼────┤\n\
+         │ 日本 │ a rather long… │  1 │\n\
+         │ x    │ short          │ 22 │\n\
+         │      │ multi          │    │\n\
+         ╰──────┴────────────────┴────╯"
+    );
+    // stripes fill the whole row, styled cells keep their style
+    let rendered = table.clone().border(Border::None).render();
+    assert!(rendered
+        .spans()
+        .contains(&StyledText::new("日本").fg(Color::Red).into_owned()));
+    assert_eq!(
+        rendered.lines()[2].spans()[0],
+        StyledText::new("x     short                 22")
+            .bg(Color::BrightBlack)
+            .into_owned()
+    );
+
+    let wrapped = table
+        .overflow(Overflow::Wrap)
+        .border(Border::Ascii)
+        .render()
+        .plain_text();
+    assert!(wrapped.contains("| 日本 | a rather long  |  1 |\n|      | description    |    |"));
+    assert!(wrapped.lines().all(|line| display_width(line) == 30));
+    println!("{wrapped}");
+    // too narrow for a single wide character
+    let narrow = Table::new()
+        .row(["日本", "a"])
+        .overflow(Overflow::Wrap)
+        .border(Border::None)
+        .max_width(4)
+        .render()
+        .plain_text();
+    assert_eq!(narrow, "…  a\n…   ");
+
+    assert!(Table::new().render().is_empty());
+}
+
+#[test]
+fn panels() {
+    let summary = panel("Summary", "Name: demo\nLicense: MIT")
+        .border(Border::Ascii)
+        .border_style(Style::new().fg(Color::Blue));
+    assert_eq!(
+        summary.render().plain_text(),
+        "+- Summary ----+\n\
+         | Name: demo   |\n\
+         | License: MIT |\n\
+         +--------------+"
+    );
+    assert_eq!(
+        summary.render().spans()[0],
+        StyledText::new("+-").fg(Color::Blue).into_owned()
+    );
+
+    // long bodies wrap, long titles are truncated
+    let narrow = Panel::new("one two three four")
+        .title("A very long title")
+        .title_align(Align::Right)
+        .padding(1, 1)
+        .width(12);
+    assert_eq!(
+        narrow.render().plain_text(),
+        "╭─ A ver… ─╮\n\
+         │          │\n\
+         │ one two  │\n\
+         │ three    │\n\
+         │ four     │\n\
+         │          │\n\
+         ╰──────────╯"
+    );
+    let expanded = Panel::new("x").border(Border::Heavy).width(10).expand();
+    assert_eq!(
+        expanded.render().plain_text(),
+        "┏━━━━━━━━┓\n┃ x      ┃\n┗━━━━━━━━┛"
+    );
+
+    assert_eq!(
+        rule("Section").width(15).render().plain_text(),
+        "─── Section ───"
+    );
+    assert_eq!(
+        Rule::new()
+            .border(Border::Ascii)
+            .width(5)
+            .render()
+            .plain_text(),
+        "-----"
+    );
+    println!("{summary}\n{}", rule("Section"));
+}
+
+#[test]
+fn diagnostics() {
+    let source = "{\n\t\"名前\": \"demo\",\n  \"port\": true,\n  \"a\": 1,\n  \"port\": 80\n}\n";
+    let span = |text: &str, n: usize| {
+        let start = source.match_indices(text).nth(n).unwrap().0;
+        start..start + text.len()
+    };
+    let diagnostic = Diagnostic::warning("duplicate key")
+        .source("config.json", source)
+        .label(Label::primary(span("\"port\"", 1), "redefined here"))
+        .label(Label::secondary(span("\"port\"", 0), "first defined here"))
+        .label(Label::secondary(span("true", 0), "with this value"))
+        .label(Label::secondary(span("\"名前\"", 0), ""));
+    assert_eq!(diagnostic.severity(), Severity::Warning);
+    // tabs and wide characters are aligned, labels on the same line stacked
+    assert_eq!(
+        diagnostic.render().plain_text(),
+        "warning: duplicate key\n \
+          --> config.json:5:3\n  \
+           |\n\
+         2 |     \"名前\": \"demo\",\n  \
+           |     ------\n\
+         3 |   \"port\": true,\n  \
+           |   ------  ---- with this value\n  \
+           |   |\n  \
+           |   first defined here\n\
+         ...\n\
+         5 |   \"port\": 80\n  \
+           |   ^^^^^^ redefined here"
+    );
+    println!("{diagnostic}");
+
+    let curly = with_theme(&Theme::default(), || {
+        diagnostic.clone().marker(Marker::Curly).render()
+    });
+    assert!(curly.spans().contains(
+        &StyledText::new("\"port\"")
+            .fg(Color::Blue)
+            .underline_style(Underline::Curly)
+            .underline_color(Color::BrightYellow)
+            .into_owned()
+    ));
+    assert!(curly
+        .plain_text()
+        .ends_with("5 |   \"port\": 80\n  |   redefined here"));
+
+    // overlapping pieces are cut off by cells, not characters
+    let stacked = Diagnostic::error("bad")
+        .source("a", "key = 1")
+        .label(Label::primary(0..1, "first"))
+        .label(Label::primary(0..1, "日本"))
+        .label(Label::primary(0..3, "last"));
+    assert!(stacked
+        .render()
+        .plain_text()
+        .ends_with("  | | 本\n  | first"));
+
+    // pointing past the end, without a source
+    let eof = Diagnostic::error("unexpected end of file")
+        .source("a.toml", "key =")
+        .label(Label::primary(5..5, "expected a value"));
+    assert!(eof
+        .render()
+        .plain_text()
+        .ends_with("1 | key =\n  |      ^ expected a value"));
+    // on the line break of CRLF line endings
+    let crlf = Diagnostic::error("bad")
+        .source("a.toml", "key = 1\r\nother = 2\r\n")
+        .label(Label::primary(8..9, "here"));
+    assert!(crlf
+        .render()
+        .plain_text()
+        .ends_with("1 | key = 1\n  |        ^ here"));
+    assert_eq!(
+        Diagnostic::new(Severity::Error, "no config found")
+            .code("E404")
+            .help("run `init` first")
+            .render()
+            .plain_text(),
+        "error[E404]: no config found\n = help: run `init` first"
+    );
+}
+
+#[test]
+fn highlighting() {
+    let theme = Theme::default();
+    let styles = |name: &str, source: &str| {
+        let diagnostic = Diagnostic::error("bad")
+            .source(name, source)
+            .label(Label::primary(0..source.len(), ""));
+        with_theme(&theme, || diagnostic.render())
+            .spans()
+            .iter()
+            .map(|span| (span.text().to_string(), span.style()))
+            .collect::<Vec<_>>()
+    };
+    let toml = styles(
+        "Config.TOML",
+        "[server] # main\nport = 8080\nname = 'a\"b' # \"quoted\"\ntls = true",
+    );
+    for (text, style) in [
+        ("[server]", theme.value_key_style),
+        ("# main", theme.hint_style),
+        ("port", theme.value_key_style),
+        ("8080", theme.value_number_style),
+        ("'a\"b'", theme.value_string_style),
+        ("# \"quoted\"", theme.hint_style),
+        ("true", theme.value_bool_style),
+    ] {
+        assert!(
+            toml.contains(&(text.to_string(), style)),
+            "{text} in {toml:?}"
+        );
+    }
+
+    let json = styles("a.json", r#"{"a\"": [-1.5e3, null, false, "x"]}"#);
+    for (text, style) in [
+        (r#""a\"""#, theme.value_key_style),
+        ("-1.5e3", theme.value_number_style),
+        ("null", theme.value_null_style),
+        ("false", theme.value_bool_style),
+        (r#""x""#, theme.value_string_style),
+    ] {
+        assert!(
+            json.contains(&(text.to_string(), style)),
+            "{text} in {json:?}"
+        );
+    }
+
+    // unknown extensions aren't highlighted, unless the language is set
+    let plain = Diagnostic::error("bad")
+        .source("a.txt", "a = 1")
+        .label(Label::primary(0..1, ""));
+    assert_eq!(Language::from_path("a.txt"), Language::Plain);
+    let number = StyledText::new("1")
+        .with_style(theme.value_number_style)
+        .into_owned();
+    assert!(!with_theme(&theme, || plain.render())
+        .spans()
+        .contains(&number));
+    assert!(
+        with_theme(&theme, || plain.language(Language::Toml).render())
+            .spans()
+            .contains(&number)
+    );
+}
+
+#[test]
+fn gradients() {
+    let text = gradient("ab\nabc", &[Color::Rgb(0, 0, 0), Color::Rgb(200, 100, 0)]);
+    let colors: Vec<_> = text
+        .spans()
+        .iter()
+        .map(|s| (s.text().to_string(), s.style().foreground()))
+        .collect();
+    // columns share their color across lines
+    assert_eq!(
+        colors,
+        [
+            ("a".into(), Some(Color::Rgb(0, 0, 0))),
+            ("b".into(), Some(Color::Rgb(100, 50, 0))),
+            ("\n".into(), None),
+            ("a".into(), Some(Color::Rgb(0, 0, 0))),
+            ("b".into(), Some(Color::Rgb(100, 50, 0))),
+            ("c".into(), Some(Color::Rgb(200, 100, 0))),
+        ]
+    );
+
+    // links survive being recolored
+    let link = Link::new("https://example.com");
+    let linked = Gradient::rainbow().apply(&StyledText::new("docs").link(link.clone()).into());
+    assert!(linked
+        .spans()
+        .iter()
+        .all(|span| span.hyperlink() == Some(&link)));
+
+    // downgraded on terminals with fewer colors
+    let orange = Color::Rgb(255, 135, 0);
+    assert_eq!(
+        orange.downgrade(ColorLevel::Ansi256),
+        Some(Color::Indexed(208))
+    );
+    let green = Color::Rgb(10, 200, 120);
+    assert_eq!(green.downgrade(ColorLevel::Ansi16), Some(Color::Green));
+    assert_eq!(
+        Color::Indexed(9).downgrade(ColorLevel::Ansi16),
+        Some(Color::BrightRed)
+    );
+    assert_eq!(orange.downgrade(ColorLevel::None), None);
+    let caps = Capabilities {
+        colors: ColorLevel::Ansi16,
+        ..Capabilities::all()
+    };
+    assert_eq!(
+        StyledText::new("x").fg(green).format_for(&caps),
+        "\x1b[32mx\x1b[0m"
+    );
+
+    println!(
+        "{}",
+        Gradient::rainbow().paint("Hello World!\nRainbow gradients")
+    );
+    reveal(
+        &Gradient::sunset().paint("Revealed!").to_string(),
+        0.01,
+        None,
+    );
+    println!();
+}